@@ -1,12 +1,50 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20251201_150154_init_db;
+mod m20261016_090000_revoked_token;
+mod m20261016_100000_event;
+mod m20261016_110000_webhook_delivery;
+mod m20261016_120000_config_network;
+mod m20261016_130000_operation_threshold;
+mod m20261016_140000_allowlist;
+mod m20261016_150000_outflow;
+mod m20261016_160000_operation_delay;
+mod m20261016_170000_cosigner_weight;
+mod m20261016_180000_config_thresholds;
+mod m20261016_190000_config_auto_approved_ops;
+mod m20261016_200000_cosigner_response;
+mod m20261016_210000_operation_indexes;
+mod m20261016_220000_processing_result;
+mod m20261016_230000_chain_status;
+mod m20261016_233000_psbt_summary;
+mod m20261016_234000_op_file_size;
+mod m20261016_235000_cosigner_veto;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20251201_150154_init_db::Migration)]
+        vec![
+            Box::new(m20251201_150154_init_db::Migration),
+            Box::new(m20261016_090000_revoked_token::Migration),
+            Box::new(m20261016_100000_event::Migration),
+            Box::new(m20261016_110000_webhook_delivery::Migration),
+            Box::new(m20261016_120000_config_network::Migration),
+            Box::new(m20261016_130000_operation_threshold::Migration),
+            Box::new(m20261016_140000_allowlist::Migration),
+            Box::new(m20261016_150000_outflow::Migration),
+            Box::new(m20261016_160000_operation_delay::Migration),
+            Box::new(m20261016_170000_cosigner_weight::Migration),
+            Box::new(m20261016_180000_config_thresholds::Migration),
+            Box::new(m20261016_190000_config_auto_approved_ops::Migration),
+            Box::new(m20261016_200000_cosigner_response::Migration),
+            Box::new(m20261016_210000_operation_indexes::Migration),
+            Box::new(m20261016_220000_processing_result::Migration),
+            Box::new(m20261016_230000_chain_status::Migration),
+            Box::new(m20261016_233000_psbt_summary::Migration),
+            Box::new(m20261016_234000_op_file_size::Migration),
+            Box::new(m20261016_235000_cosigner_veto::Migration),
+        ]
     }
 }
@@ -0,0 +1,71 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Event::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Event::Idx))
+                    .col(tiny_unsigned(Event::Type))
+                    .col(integer(Event::OperationIdx))
+                    .col(integer_null(Event::CosignerIdx))
+                    .col(big_unsigned(Event::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-event-operationidx")
+                            .from(Event::Table, Event::OperationIdx)
+                            .to(Operation::Table, Operation::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-event-cosigneridx")
+                            .from(Event::Table, Event::CosignerIdx)
+                            .to(Cosigner::Table, Cosigner::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Event::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Idx,
+    Type,
+    OperationIdx,
+    CosignerIdx,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Operation {
+    Table,
+    Idx,
+}
+
+#[derive(DeriveIden)]
+enum Cosigner {
+    Table,
+    Idx,
+}
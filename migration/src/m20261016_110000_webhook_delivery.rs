@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDelivery::Table)
+                    .if_not_exists()
+                    .col(pk_auto(WebhookDelivery::Idx))
+                    .col(integer(WebhookDelivery::EventIdx))
+                    .col(string(WebhookDelivery::Url))
+                    .col(text_null(WebhookDelivery::Payload))
+                    .col(tiny_unsigned(WebhookDelivery::Status))
+                    .col(unsigned(WebhookDelivery::Attempts))
+                    .col(big_unsigned(WebhookDelivery::NextAttemptAt))
+                    .col(string_null(WebhookDelivery::LastError))
+                    .col(big_unsigned_null(WebhookDelivery::DeliveredAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-webhookdelivery-eventidx")
+                            .from(WebhookDelivery::Table, WebhookDelivery::EventIdx)
+                            .to(Event::Table, Event::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-webhookdelivery-status-nextattemptat")
+                    .table(WebhookDelivery::Table)
+                    .col(WebhookDelivery::Status)
+                    .col(WebhookDelivery::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebhookDelivery::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebhookDelivery {
+    Table,
+    Idx,
+    EventIdx,
+    Url,
+    Payload,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    LastError,
+    DeliveredAt,
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Idx,
+}
@@ -0,0 +1,87 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AllowlistAddress::Table)
+                    .if_not_exists()
+                    .col(pk_auto(AllowlistAddress::Idx))
+                    .col(string_uniq(AllowlistAddress::Address))
+                    .col(integer(AllowlistAddress::OperationIdx))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-allowlistaddress-operationidx")
+                            .from(AllowlistAddress::Table, AllowlistAddress::OperationIdx)
+                            .to(Operation::Table, Operation::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AllowlistUpdate::Table)
+                    .if_not_exists()
+                    .col(pk_auto(AllowlistUpdate::Idx))
+                    .col(integer(AllowlistUpdate::OperationIdx))
+                    .col(string(AllowlistUpdate::Address))
+                    .col(boolean(AllowlistUpdate::Add))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-allowlistupdate-operationidx")
+                            .from(AllowlistUpdate::Table, AllowlistUpdate::OperationIdx)
+                            .to(Operation::Table, Operation::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AllowlistUpdate::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AllowlistAddress::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AllowlistAddress {
+    Table,
+    Idx,
+    Address,
+    OperationIdx,
+}
+
+#[derive(DeriveIden)]
+enum AllowlistUpdate {
+    Table,
+    Idx,
+    OperationIdx,
+    Address,
+    Add,
+}
+
+#[derive(DeriveIden)]
+enum Operation {
+    Table,
+    Idx,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Outflow::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Outflow::Idx))
+                    .col(integer_uniq(Outflow::OperationIdx))
+                    .col(big_integer(Outflow::AmountSat))
+                    .col(big_integer(Outflow::ApprovedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-outflow-operationidx")
+                            .from(Outflow::Table, Outflow::OperationIdx)
+                            .to(Operation::Table, Operation::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-outflow-approvedat")
+                    .table(Outflow::Table)
+                    .col(Outflow::ApprovedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Outflow::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Outflow {
+    Table,
+    Idx,
+    OperationIdx,
+    AmountSat,
+    ApprovedAt,
+}
+
+#[derive(DeriveIden)]
+enum Operation {
+    Table,
+    Idx,
+}
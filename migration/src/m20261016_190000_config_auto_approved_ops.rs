@@ -0,0 +1,43 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // services configured before this option was introduced auto-approved these types
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Config::Table)
+                    .add_column(
+                        text(Config::AutoApprovedOps)
+                            .default(r#"["Issuance","BlindReceive","WitnessReceive"]"#),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Config::Table)
+                    .drop_column(Config::AutoApprovedOps)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Config {
+    Table,
+    AutoApprovedOps,
+}
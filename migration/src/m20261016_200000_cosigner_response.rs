@@ -0,0 +1,136 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CosignerResponse::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CosignerResponse::Idx))
+                    .col(integer(CosignerResponse::CosignerIdx))
+                    .col(integer(CosignerResponse::OperationIdx))
+                    .col(boolean(CosignerResponse::Ack))
+                    .col(integer_null(CosignerResponse::PsbtOpFileIdx))
+                    .col(big_integer(CosignerResponse::RespondedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-cosignerresponse-psbtopfileidx")
+                            .from(CosignerResponse::Table, CosignerResponse::PsbtOpFileIdx)
+                            .to(OpFile::Table, OpFile::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-cosignerresponse-cosigneridx")
+                            .from(CosignerResponse::Table, CosignerResponse::CosignerIdx)
+                            .to(Cosigner::Table, Cosigner::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-cosignerresponse-operationidx")
+                            .from(CosignerResponse::Table, CosignerResponse::OperationIdx)
+                            .to(Operation::Table, Operation::Idx)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-cosignerresponse-operationidx")
+                    .table(CosignerResponse::Table)
+                    .col(CosignerResponse::OperationIdx)
+                    .to_owned(),
+            )
+            .await?;
+
+        // seed the history with the responses given so far
+        let insert = Query::insert()
+            .into_table(CosignerResponse::Table)
+            .columns([
+                CosignerResponse::CosignerIdx,
+                CosignerResponse::OperationIdx,
+                CosignerResponse::Ack,
+                CosignerResponse::PsbtOpFileIdx,
+                CosignerResponse::RespondedAt,
+            ])
+            .select_from(
+                Query::select()
+                    .columns([
+                        CosignerOpStatus::CosignerIdx,
+                        CosignerOpStatus::OperationIdx,
+                        CosignerOpStatus::Ack,
+                        CosignerOpStatus::PsbtOpFileIdx,
+                        CosignerOpStatus::RespondedAt,
+                    ])
+                    .from(CosignerOpStatus::Table)
+                    .and_where(Expr::col(CosignerOpStatus::Ack).is_not_null())
+                    .and_where(Expr::col(CosignerOpStatus::RespondedAt).is_not_null())
+                    .order_by(CosignerOpStatus::RespondedAt, Order::Asc)
+                    .to_owned(),
+            )
+            .map_err(|e| DbErr::Migration(e.to_string()))?
+            .to_owned();
+        manager.exec_stmt(insert).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CosignerResponse::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum CosignerResponse {
+    Table,
+    Idx,
+    CosignerIdx,
+    OperationIdx,
+    Ack,
+    PsbtOpFileIdx,
+    RespondedAt,
+}
+
+#[derive(DeriveIden)]
+enum CosignerOpStatus {
+    Table,
+    CosignerIdx,
+    OperationIdx,
+    Ack,
+    PsbtOpFileIdx,
+    RespondedAt,
+}
+
+#[derive(DeriveIden)]
+enum Cosigner {
+    Table,
+    Idx,
+}
+
+#[derive(DeriveIden)]
+enum OpFile {
+    Table,
+    Idx,
+}
+
+#[derive(DeriveIden)]
+enum Operation {
+    Table,
+    Idx,
+}
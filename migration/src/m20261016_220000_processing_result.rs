@@ -0,0 +1,78 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports adding one column per statement
+        for column in [
+            boolean_null(CosignerOpStatus::ResultSuccess),
+            string_null(CosignerOpStatus::ResultTxid),
+            string_null(CosignerOpStatus::ResultStateHash),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(CosignerOpStatus::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Operation::Table)
+                    .add_column(boolean(Operation::Divergent).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Operation::Table)
+                    .drop_column(Operation::Divergent)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            CosignerOpStatus::ResultStateHash,
+            CosignerOpStatus::ResultTxid,
+            CosignerOpStatus::ResultSuccess,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(CosignerOpStatus::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum CosignerOpStatus {
+    Table,
+    ResultSuccess,
+    ResultTxid,
+    ResultStateHash,
+}
+
+#[derive(DeriveIden)]
+enum Operation {
+    Table,
+    Divergent,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports adding one column per statement
+        for column in [
+            tiny_unsigned_null(Operation::ChainStatus),
+            string_null(Operation::Txid),
+            unsigned_null(Operation::BlockHeight),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Operation::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Operation::BlockHeight,
+            Operation::Txid,
+            Operation::ChainStatus,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Operation::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Operation {
+    Table,
+    ChainStatus,
+    Txid,
+    BlockHeight,
+}
@@ -16,10 +16,13 @@ use crate::{
 };
 
 const WATCH_ONLY_ALLOWED_ROUTES: &[&str] = &[
+    "/events",
     "/info",
     "/getoperationbyidx",
     "/getcurrentaddressindices",
     "/getfile",
+    "/listoperations",
+    "/syncoperations",
 ];
 
 fn is_watch_only_allowed(path: &str) -> bool {
@@ -34,6 +37,20 @@ fn is_token_expired(token: &Biscuit) -> bool {
         .is_err()
 }
 
+/// Get the role and, for cosigners, the xPub stated by a token
+pub(crate) fn get_token_role_and_xpub(token: &Biscuit) -> Option<(String, Option<String>)> {
+    let mut authorizer = token.authorizer().ok()?;
+    let role = authorizer
+        .query("data($r) <- role($r)")
+        .ok()
+        .and_then(|v: Vec<(String,)>| v.first().map(|r| r.0.clone()))?;
+    let xpub = authorizer
+        .query("data($x) <- xpub($x)")
+        .ok()
+        .and_then(|v: Vec<(String,)>| v.first().map(|x| x.0.clone()));
+    Some((role, xpub))
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct AuthenticatedCosigner {
     pub(crate) xpub: String,
@@ -112,17 +129,27 @@ pub(crate) async fn conditional_auth_middleware(
         return Err(AuthError::Unauthorized);
     }
 
+    // check if the token has been revoked
+    let revocation_ids = token
+        .revocation_identifiers()
+        .iter()
+        .map(hex::encode)
+        .collect();
+    let is_revoked = app_state
+        .database
+        .has_revoked_token(revocation_ids)
+        .await
+        .map_err(|e| {
+            tracing::error!("failed to check token revocation: {e}");
+            AuthError::Unauthorized
+        })?;
+    if is_revoked {
+        tracing::warn!("attempted to use a revoked token");
+        return Err(AuthError::Unauthorized);
+    }
+
     // determine the authenticated user based on the role and the xPub
-    let mut authorizer = token.authorizer().map_err(|_| AuthError::Unauthorized)?;
-    let role = authorizer
-        .query("data($r) <- role($r)")
-        .ok()
-        .and_then(|v: Vec<(String,)>| v.first().map(|r| r.0.clone()))
-        .ok_or(AuthError::Unauthorized)?;
-    let xpub = authorizer
-        .query("data($x) <- xpub($x)")
-        .ok()
-        .and_then(|v: Vec<(String,)>| v.first().map(|x| x.0.clone()));
+    let (role, xpub) = get_token_role_and_xpub(&token).ok_or(AuthError::Unauthorized)?;
     let user = match (role.as_str(), xpub) {
         ("cosigner", Some(xpub)) => {
             let idx = app_state
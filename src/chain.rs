@@ -0,0 +1,69 @@
+use std::{future::Future, time::Duration};
+
+use amplify::s;
+use bitcoin::Txid;
+use serde::Deserialize;
+
+// timeout for a single chain source request
+const CHAIN_SOURCE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A source of on-chain data, used to track the confirmation of broadcast operations
+pub(crate) trait ChainSource: Send + Sync + 'static {
+    /// Get the height of the block including the given transaction, `None` if the transaction
+    /// is not confirmed (or not known at all)
+    fn get_tx_block_height(
+        &self,
+        txid: &Txid,
+    ) -> impl Future<Output = Result<Option<u32>, String>> + Send;
+}
+
+/// A chain source backed by an Esplora API
+pub(crate) struct EsploraChainSource {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct EsploraTxStatus {
+    confirmed: bool,
+    block_height: Option<u32>,
+}
+
+impl EsploraChainSource {
+    pub(crate) fn new(url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(CHAIN_SOURCE_REQUEST_TIMEOUT)
+            .build()
+            .expect("valid HTTP client configuration");
+        Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl ChainSource for EsploraChainSource {
+    async fn get_tx_block_height(&self, txid: &Txid) -> Result<Option<u32>, String> {
+        let res = self
+            .client
+            .get(format!("{}/tx/{txid}/status", self.url))
+            .send()
+            .await
+            .map_err(|e| format!("request failed: {e}"))?;
+        if res.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !res.status().is_success() {
+            return Err(format!("unexpected response status {}", res.status()));
+        }
+        let status: EsploraTxStatus = res
+            .json()
+            .await
+            .map_err(|e| format!("invalid response: {e}"))?;
+        match (status.confirmed, status.block_height) {
+            (true, Some(block_height)) => Ok(Some(block_height)),
+            (true, None) => Err(s!("confirmed transaction without a block height")),
+            (false, _) => Ok(None),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 use sea_orm::entity::prelude::*;
 
+use crate::routes::BitcoinNetwork;
+
 #[derive(Copy, Clone, Default, Debug, DeriveEntity)]
 pub struct Entity;
 
@@ -16,6 +18,9 @@ pub struct Model {
     pub idx: i32,
     pub threshold_colored: u8,
     pub threshold_vanilla: u8,
+    pub network: Option<BitcoinNetwork>,
+    pub thresholds: String,
+    pub auto_approved_ops: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -23,6 +28,9 @@ pub enum Column {
     Idx,
     ThresholdColored,
     ThresholdVanilla,
+    Network,
+    Thresholds,
+    AutoApprovedOps,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -47,6 +55,9 @@ impl ColumnTrait for Column {
             Self::Idx => ColumnType::Integer.def(),
             Self::ThresholdColored => ColumnType::SmallInteger.def(),
             Self::ThresholdVanilla => ColumnType::SmallInteger.def(),
+            Self::Network => ColumnType::SmallInteger.def().null(),
+            Self::Thresholds => ColumnType::Text.def(),
+            Self::AutoApprovedOps => ColumnType::Text.def(),
         }
     }
 }
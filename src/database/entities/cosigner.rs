@@ -15,12 +15,16 @@ impl EntityName for Entity {
 pub struct Model {
     pub idx: i32,
     pub xpub: String,
+    pub weight: u8,
+    pub veto: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
 pub enum Column {
     Idx,
     Xpub,
+    Weight,
+    Veto,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -38,6 +42,8 @@ impl PrimaryKeyTrait for PrimaryKey {
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
     CosignerOpStatus,
+    CosignerResponse,
+    Event,
     Operation,
 }
 
@@ -47,6 +53,8 @@ impl ColumnTrait for Column {
         match self {
             Self::Idx => ColumnType::Integer.def(),
             Self::Xpub => ColumnType::String(StringLen::None).def().unique(),
+            Self::Weight => ColumnType::TinyUnsigned.def(),
+            Self::Veto => ColumnType::Boolean.def(),
         }
     }
 }
@@ -55,6 +63,8 @@ impl RelationTrait for Relation {
     fn def(&self) -> RelationDef {
         match self {
             Self::CosignerOpStatus => Entity::has_many(super::cosigner_op_status::Entity).into(),
+            Self::CosignerResponse => Entity::has_many(super::cosigner_response::Entity).into(),
+            Self::Event => Entity::has_many(super::event::Entity).into(),
             Self::Operation => Entity::has_many(super::operation::Entity).into(),
         }
     }
@@ -66,6 +76,18 @@ impl Related<super::cosigner_op_status::Entity> for Entity {
     }
 }
 
+impl Related<super::cosigner_response::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CosignerResponse.def()
+    }
+}
+
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
 impl Related<super::operation::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Operation.def()
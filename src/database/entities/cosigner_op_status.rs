@@ -20,6 +20,9 @@ pub struct Model {
     pub responded_at: Option<i64>,
     pub processed_at: Option<i64>,
     pub psbt_op_file_idx: Option<i32>,
+    pub result_success: Option<bool>,
+    pub result_txid: Option<String>,
+    pub result_state_hash: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -31,6 +34,9 @@ pub enum Column {
     RespondedAt,
     ProcessedAt,
     PsbtOpFileIdx,
+    ResultSuccess,
+    ResultTxid,
+    ResultStateHash,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -63,6 +69,9 @@ impl ColumnTrait for Column {
             Self::RespondedAt => ColumnType::BigInteger.def().null(),
             Self::ProcessedAt => ColumnType::BigInteger.def().null(),
             Self::PsbtOpFileIdx => ColumnType::Integer.def().null(),
+            Self::ResultSuccess => ColumnType::Boolean.def().null(),
+            Self::ResultTxid => ColumnType::String(StringLen::None).def().null(),
+            Self::ResultStateHash => ColumnType::String(StringLen::None).def().null(),
         }
     }
 }
@@ -2,9 +2,16 @@
 
 pub mod prelude;
 
+pub mod allowlist_address;
+pub mod allowlist_update;
 pub mod config;
 pub mod cosigner;
 pub mod cosigner_op_status;
+pub mod cosigner_response;
+pub mod event;
 pub mod next_address_index;
 pub mod op_file;
 pub mod operation;
+pub mod outflow;
+pub mod revoked_token;
+pub mod webhook_delivery;
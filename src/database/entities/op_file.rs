@@ -19,6 +19,7 @@ pub struct Model {
     pub file_id: String,
     pub r#type: FileType,
     pub operation_idx: i32,
+    pub size_bytes: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -27,6 +28,7 @@ pub enum Column {
     FileId,
     Type,
     OperationIdx,
+    SizeBytes,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -44,6 +46,7 @@ impl PrimaryKeyTrait for PrimaryKey {
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
     CosignerOpStatus,
+    CosignerResponse,
     Operation,
 }
 
@@ -55,6 +58,7 @@ impl ColumnTrait for Column {
             Self::FileId => ColumnType::String(StringLen::None).def(),
             Self::Type => ColumnType::SmallInteger.def(),
             Self::OperationIdx => ColumnType::Integer.def(),
+            Self::SizeBytes => ColumnType::BigInteger.def().null(),
         }
     }
 }
@@ -63,6 +67,7 @@ impl RelationTrait for Relation {
     fn def(&self) -> RelationDef {
         match self {
             Self::CosignerOpStatus => Entity::has_many(super::cosigner_op_status::Entity).into(),
+            Self::CosignerResponse => Entity::has_many(super::cosigner_response::Entity).into(),
             Self::Operation => Entity::belongs_to(super::operation::Entity)
                 .from(Column::OperationIdx)
                 .to(super::operation::Column::Idx)
@@ -77,6 +82,12 @@ impl Related<super::cosigner_op_status::Entity> for Entity {
     }
 }
 
+impl Related<super::cosigner_response::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CosignerResponse.def()
+    }
+}
+
 impl Related<super::operation::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Operation.def()
@@ -2,7 +2,7 @@
 
 use sea_orm::entity::prelude::*;
 
-use crate::routes::{OperationStatus, OperationType};
+use crate::routes::{ChainStatus, OperationStatus, OperationType};
 
 #[derive(Copy, Clone, Default, Debug, DeriveEntity)]
 pub struct Entity;
@@ -20,6 +20,13 @@ pub struct Model {
     pub status: OperationStatus,
     pub created_at: i64,
     pub initiator_idx: i32,
+    pub threshold: Option<u8>,
+    pub delay_ends_at: Option<i64>,
+    pub divergent: bool,
+    pub chain_status: Option<ChainStatus>,
+    pub txid: Option<String>,
+    pub block_height: Option<u32>,
+    pub psbt_summary: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -29,6 +36,13 @@ pub enum Column {
     Status,
     CreatedAt,
     InitiatorIdx,
+    Threshold,
+    DelayEndsAt,
+    Divergent,
+    ChainStatus,
+    Txid,
+    BlockHeight,
+    PsbtSummary,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -45,9 +59,14 @@ impl PrimaryKeyTrait for PrimaryKey {
 
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
+    AllowlistAddress,
+    AllowlistUpdate,
     Cosigner,
     CosignerOpStatus,
+    CosignerResponse,
+    Event,
     OpFile,
+    Outflow,
 }
 
 impl ColumnTrait for Column {
@@ -59,6 +78,13 @@ impl ColumnTrait for Column {
             Self::Status => ColumnType::SmallInteger.def(),
             Self::CreatedAt => ColumnType::BigInteger.def(),
             Self::InitiatorIdx => ColumnType::Integer.def(),
+            Self::Threshold => ColumnType::SmallInteger.def().null(),
+            Self::DelayEndsAt => ColumnType::BigInteger.def().null(),
+            Self::Divergent => ColumnType::Boolean.def(),
+            Self::ChainStatus => ColumnType::SmallInteger.def().null(),
+            Self::Txid => ColumnType::String(StringLen::None).def().null(),
+            Self::BlockHeight => ColumnType::Integer.def().null(),
+            Self::PsbtSummary => ColumnType::Text.def().null(),
         }
     }
 }
@@ -66,16 +92,33 @@ impl ColumnTrait for Column {
 impl RelationTrait for Relation {
     fn def(&self) -> RelationDef {
         match self {
+            Self::AllowlistAddress => Entity::has_many(super::allowlist_address::Entity).into(),
+            Self::AllowlistUpdate => Entity::has_many(super::allowlist_update::Entity).into(),
             Self::Cosigner => Entity::belongs_to(super::cosigner::Entity)
                 .from(Column::InitiatorIdx)
                 .to(super::cosigner::Column::Idx)
                 .into(),
             Self::CosignerOpStatus => Entity::has_many(super::cosigner_op_status::Entity).into(),
+            Self::CosignerResponse => Entity::has_many(super::cosigner_response::Entity).into(),
+            Self::Event => Entity::has_many(super::event::Entity).into(),
             Self::OpFile => Entity::has_many(super::op_file::Entity).into(),
+            Self::Outflow => Entity::has_one(super::outflow::Entity).into(),
         }
     }
 }
 
+impl Related<super::allowlist_address::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AllowlistAddress.def()
+    }
+}
+
+impl Related<super::allowlist_update::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AllowlistUpdate.def()
+    }
+}
+
 impl Related<super::cosigner::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Cosigner.def()
@@ -88,10 +131,28 @@ impl Related<super::cosigner_op_status::Entity> for Entity {
     }
 }
 
+impl Related<super::cosigner_response::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CosignerResponse.def()
+    }
+}
+
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
 impl Related<super::op_file::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::OpFile.def()
     }
 }
 
+impl Related<super::outflow::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Outflow.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,8 +1,15 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+pub use super::allowlist_address::Entity as AllowlistAddress;
+pub use super::allowlist_update::Entity as AllowlistUpdate;
 pub use super::config::Entity as Config;
 pub use super::cosigner::Entity as Cosigner;
 pub use super::cosigner_op_status::Entity as CosignerOpStatus;
+pub use super::cosigner_response::Entity as CosignerResponse;
+pub use super::event::Entity as Event;
 pub use super::next_address_index::Entity as NextAddressIndex;
 pub use super::op_file::Entity as OpFile;
 pub use super::operation::Entity as Operation;
+pub use super::outflow::Entity as Outflow;
+pub use super::revoked_token::Entity as RevokedToken;
+pub use super::webhook_delivery::Entity as WebhookDelivery;
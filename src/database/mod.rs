@@ -1,17 +1,28 @@
 pub(crate) mod entities;
 
-use amplify::s;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
-    ModelTrait, QueryFilter, QueryOrder, TransactionTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait, sea_query::Query,
 };
 
 use crate::{
     database::entities::{prelude::*, *},
     error::{APIError, AppError},
-    routes::OperationStatus,
+    routes::{ChainStatus, FileType, OperationStatus, OperationType, WebhookDeliveryStatus},
 };
 
+/// Criteria to select operations, all set ones must match
+#[derive(Default)]
+pub(crate) struct OperationFilter {
+    pub(crate) after_idx: Option<i32>,
+    pub(crate) status: Option<OperationStatus>,
+    pub(crate) r#type: Option<OperationType>,
+    pub(crate) initiator_idx: Option<i32>,
+    pub(crate) created_from: Option<i64>,
+    pub(crate) created_to: Option<i64>,
+    pub(crate) unprocessed_by: Option<i32>,
+}
+
 pub struct AppDatabase {
     connection: DatabaseConnection,
 }
@@ -29,6 +40,28 @@ impl AppDatabase {
         &self.connection
     }
 
+    pub(crate) async fn set_allowlist_address(
+        &self,
+        address: allowlist_address::ActiveModel,
+        txn: &DatabaseTransaction,
+    ) -> Result<i32, APIError> {
+        Ok(AllowlistAddress::insert(address)
+            .exec(txn)
+            .await?
+            .last_insert_id)
+    }
+
+    pub(crate) async fn set_allowlist_updates(
+        &self,
+        updates: Vec<allowlist_update::ActiveModel>,
+        txn: &DatabaseTransaction,
+    ) -> Result<i32, APIError> {
+        Ok(AllowlistUpdate::insert_many(updates)
+            .exec(txn)
+            .await?
+            .last_insert_id)
+    }
+
     pub(crate) async fn set_config(&self, config: config::ActiveModel) -> Result<i32, AppError> {
         let res = Config::insert(config).exec(self.get_connection()).await?;
         Ok(res.last_insert_id)
@@ -45,6 +78,17 @@ impl AppDatabase {
             .last_insert_id)
     }
 
+    pub(crate) async fn set_cosigner_response(
+        &self,
+        response: cosigner_response::ActiveModel,
+        txn: &DatabaseTransaction,
+    ) -> Result<i32, APIError> {
+        Ok(CosignerResponse::insert(response)
+            .exec(txn)
+            .await?
+            .last_insert_id)
+    }
+
     pub(crate) async fn set_cosigners(
         &self,
         cosigners: Vec<cosigner::ActiveModel>,
@@ -55,6 +99,14 @@ impl AppDatabase {
             .last_insert_id)
     }
 
+    pub(crate) async fn set_event(
+        &self,
+        event: event::ActiveModel,
+        txn: &DatabaseTransaction,
+    ) -> Result<event::Model, APIError> {
+        Ok(event.insert(txn).await?)
+    }
+
     pub(crate) async fn set_next_address_index(
         &self,
         index: next_address_index::ActiveModel,
@@ -81,6 +133,52 @@ impl AppDatabase {
         Ok(Operation::insert(operation).exec(txn).await?.last_insert_id)
     }
 
+    pub(crate) async fn set_outflow(
+        &self,
+        outflow: outflow::ActiveModel,
+        txn: &DatabaseTransaction,
+    ) -> Result<i32, APIError> {
+        Ok(Outflow::insert(outflow).exec(txn).await?.last_insert_id)
+    }
+
+    pub(crate) async fn set_revoked_token(
+        &self,
+        revoked_token: revoked_token::ActiveModel,
+    ) -> Result<i32, APIError> {
+        Ok(RevokedToken::insert(revoked_token)
+            .exec(self.get_connection())
+            .await?
+            .last_insert_id)
+    }
+
+    pub(crate) async fn set_webhook_delivery(
+        &self,
+        delivery: webhook_delivery::ActiveModel,
+        txn: &DatabaseTransaction,
+    ) -> Result<i32, APIError> {
+        Ok(WebhookDelivery::insert(delivery)
+            .exec(txn)
+            .await?
+            .last_insert_id)
+    }
+
+    pub(crate) async fn delete_allowlist_address(
+        &self,
+        address: &str,
+        txn: &DatabaseTransaction,
+    ) -> Result<(), APIError> {
+        AllowlistAddress::delete_many()
+            .filter(allowlist_address::Column::Address.eq(address))
+            .exec(txn)
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn update_config(&self, config: config::ActiveModel) -> Result<(), AppError> {
+        config.update(self.get_connection()).await?;
+        Ok(())
+    }
+
     pub(crate) async fn update_cosigner_op_status(
         &self,
         status: cosigner_op_status::ActiveModel,
@@ -112,15 +210,16 @@ impl AppDatabase {
         Ok(())
     }
 
-    pub(crate) async fn get_config(&self) -> Result<Option<config::Model>, AppError> {
-        Ok(Config::find().one(self.get_connection()).await?)
+    pub(crate) async fn update_webhook_delivery(
+        &self,
+        delivery: webhook_delivery::ActiveModel,
+    ) -> Result<(), APIError> {
+        delivery.update(self.get_connection()).await?;
+        Ok(())
     }
 
-    pub(crate) async fn get_cosigner_by_idx(
-        &self,
-        idx: i32,
-    ) -> Result<Option<cosigner::Model>, APIError> {
-        Ok(Cosigner::find_by_id(idx).one(self.get_connection()).await?)
+    pub(crate) async fn get_config(&self) -> Result<Option<config::Model>, AppError> {
+        Ok(Config::find().one(self.get_connection()).await?)
     }
 
     pub(crate) async fn get_cosigner_op_status_entry(
@@ -135,26 +234,24 @@ impl AppDatabase {
             .await?)
     }
 
-    pub(crate) async fn get_cosigner_op_status_with_cosigners_by_operation_idx(
+    pub(crate) async fn get_delayed_operation(&self) -> Result<Option<operation::Model>, APIError> {
+        Ok(Operation::find()
+            .filter(operation::Column::Status.eq(OperationStatus::ApprovedPendingDelay))
+            .one(self.get_connection())
+            .await?)
+    }
+
+    pub(crate) async fn get_first_op_file_by_type(
         &self,
         operation_idx: i32,
-    ) -> Result<Vec<(cosigner_op_status::Model, cosigner::Model)>, APIError> {
-        let status_entries = CosignerOpStatus::find()
-            .filter(cosigner_op_status::Column::OperationIdx.eq(operation_idx))
-            .all(self.get_connection())
-            .await?;
-        let mut result = Vec::new();
-        for status in status_entries {
-            let cosigner = status
-                .find_related(Cosigner)
-                .one(self.get_connection())
-                .await?
-                .ok_or(APIError::Unexpected(s!(
-                    "CosignerOpStatus entry missing cosigner"
-                )))?;
-            result.push((status, cosigner));
-        }
-        Ok(result)
+        r#type: FileType,
+    ) -> Result<Option<op_file::Model>, APIError> {
+        Ok(OpFile::find()
+            .filter(op_file::Column::OperationIdx.eq(operation_idx))
+            .filter(op_file::Column::Type.eq(r#type))
+            .order_by_asc(op_file::Column::Idx)
+            .one(self.get_connection())
+            .await?)
     }
 
     pub(crate) async fn get_last_cosigner_processed_op_idx(
@@ -198,28 +295,85 @@ impl AppDatabase {
         Ok(OpFile::find_by_id(idx).one(self.get_connection()).await?)
     }
 
-    pub(crate) async fn get_op_files_by_operation_idx(
+    pub(crate) async fn get_operation_by_idx(
         &self,
-        operation_idx: i32,
-    ) -> Result<Vec<op_file::Model>, APIError> {
-        Ok(Operation::find_by_id(operation_idx)
+        idx: i32,
+        txn: Option<&DatabaseTransaction>,
+    ) -> Result<Option<operation::Model>, APIError> {
+        let query = Operation::find_by_id(idx);
+        Ok(if let Some(txn) = txn {
+            query.one(txn).await?
+        } else {
+            query.one(self.get_connection()).await?
+        })
+    }
+
+    pub(crate) async fn get_pending_operation(&self) -> Result<Option<operation::Model>, APIError> {
+        Ok(Operation::find()
+            .filter(operation::Column::Status.eq(OperationStatus::Pending))
             .one(self.get_connection())
-            .await?
-            .ok_or(APIError::Unexpected(s!("operation not found")))?
-            .find_related(OpFile)
-            .all(self.get_connection())
             .await?)
     }
 
-    pub(crate) async fn get_operation_by_idx(
+    pub(crate) async fn get_revoked_token(
         &self,
-        idx: i32,
-    ) -> Result<Option<operation::Model>, APIError> {
-        Ok(Operation::find_by_id(idx)
+        revocation_id: &str,
+    ) -> Result<Option<revoked_token::Model>, APIError> {
+        Ok(RevokedToken::find()
+            .filter(revoked_token::Column::RevocationId.eq(revocation_id))
             .one(self.get_connection())
             .await?)
     }
 
+    pub(crate) async fn iter_allowlist_addresses(
+        &self,
+    ) -> Result<Vec<allowlist_address::Model>, APIError> {
+        Ok(AllowlistAddress::find()
+            .order_by_asc(allowlist_address::Column::Idx)
+            .all(self.get_connection())
+            .await?)
+    }
+
+    pub(crate) async fn iter_allowlist_updates_by_operation_idx(
+        &self,
+        operation_idx: i32,
+        txn: Option<&DatabaseTransaction>,
+    ) -> Result<Vec<allowlist_update::Model>, APIError> {
+        let query = AllowlistUpdate::find()
+            .filter(allowlist_update::Column::OperationIdx.eq(operation_idx))
+            .order_by_asc(allowlist_update::Column::Idx);
+        Ok(if let Some(txn) = txn {
+            query.all(txn).await?
+        } else {
+            query.all(self.get_connection()).await?
+        })
+    }
+
+    pub(crate) async fn iter_allowlist_updates_by_operation_idxs(
+        &self,
+        operation_idxs: &[i32],
+        txn: Option<&DatabaseTransaction>,
+    ) -> Result<Vec<allowlist_update::Model>, APIError> {
+        let query = AllowlistUpdate::find()
+            .filter(allowlist_update::Column::OperationIdx.is_in(operation_idxs.to_vec()))
+            .order_by_asc(allowlist_update::Column::Idx);
+        Ok(if let Some(txn) = txn {
+            query.all(txn).await?
+        } else {
+            query.all(self.get_connection()).await?
+        })
+    }
+
+    pub(crate) async fn iter_broadcast_operations(
+        &self,
+    ) -> Result<Vec<operation::Model>, APIError> {
+        Ok(Operation::find()
+            .filter(operation::Column::ChainStatus.eq(ChainStatus::Broadcast))
+            .order_by_asc(operation::Column::Idx)
+            .all(self.get_connection())
+            .await?)
+    }
+
     pub(crate) async fn iter_cosigners<E>(&self) -> Result<Vec<cosigner::Model>, E>
     where
         E: From<DbErr>,
@@ -238,9 +392,165 @@ impl AppDatabase {
             .await?)
     }
 
+    pub(crate) async fn iter_cosigner_op_status_by_operation_idxs(
+        &self,
+        operation_idxs: &[i32],
+        txn: Option<&DatabaseTransaction>,
+    ) -> Result<Vec<cosigner_op_status::Model>, APIError> {
+        let query = CosignerOpStatus::find()
+            .filter(cosigner_op_status::Column::OperationIdx.is_in(operation_idxs.to_vec()))
+            .order_by_asc(cosigner_op_status::Column::Idx);
+        Ok(if let Some(txn) = txn {
+            query.all(txn).await?
+        } else {
+            query.all(self.get_connection()).await?
+        })
+    }
+
+    pub(crate) async fn iter_cosigner_responses_by_operation_idxs(
+        &self,
+        operation_idxs: &[i32],
+        txn: Option<&DatabaseTransaction>,
+    ) -> Result<Vec<cosigner_response::Model>, APIError> {
+        let query = CosignerResponse::find()
+            .filter(cosigner_response::Column::OperationIdx.is_in(operation_idxs.to_vec()))
+            .order_by_asc(cosigner_response::Column::Idx);
+        Ok(if let Some(txn) = txn {
+            query.all(txn).await?
+        } else {
+            query.all(self.get_connection()).await?
+        })
+    }
+
+    pub(crate) async fn iter_due_webhook_deliveries(
+        &self,
+        now: i64,
+    ) -> Result<Vec<webhook_delivery::Model>, APIError> {
+        Ok(WebhookDelivery::find()
+            .filter(webhook_delivery::Column::Status.eq(WebhookDeliveryStatus::Pending))
+            .filter(webhook_delivery::Column::NextAttemptAt.lte(now))
+            .order_by_asc(webhook_delivery::Column::Idx)
+            .all(self.get_connection())
+            .await?)
+    }
+
+    pub(crate) async fn iter_events_after_idx(
+        &self,
+        idx: i32,
+    ) -> Result<Vec<event::Model>, APIError> {
+        Ok(Event::find()
+            .filter(event::Column::Idx.gt(idx))
+            .order_by_asc(event::Column::Idx)
+            .all(self.get_connection())
+            .await?)
+    }
+
+    pub(crate) async fn iter_op_files_by_operation_idxs(
+        &self,
+        operation_idxs: &[i32],
+        txn: Option<&DatabaseTransaction>,
+    ) -> Result<Vec<op_file::Model>, APIError> {
+        let query = OpFile::find()
+            .filter(op_file::Column::OperationIdx.is_in(operation_idxs.to_vec()))
+            .order_by_asc(op_file::Column::Idx);
+        Ok(if let Some(txn) = txn {
+            query.all(txn).await?
+        } else {
+            query.all(self.get_connection()).await?
+        })
+    }
+
+    pub(crate) async fn iter_operations(
+        &self,
+        filter: &OperationFilter,
+        limit: Option<u64>,
+    ) -> Result<Vec<operation::Model>, APIError> {
+        let mut query = Operation::find();
+        if let Some(after_idx) = filter.after_idx {
+            query = query.filter(operation::Column::Idx.gt(after_idx));
+        }
+        if let Some(status) = filter.status {
+            query = query.filter(operation::Column::Status.eq(status));
+        }
+        if let Some(r#type) = filter.r#type {
+            query = query.filter(operation::Column::Type.eq(r#type));
+        }
+        if let Some(initiator_idx) = filter.initiator_idx {
+            query = query.filter(operation::Column::InitiatorIdx.eq(initiator_idx));
+        }
+        if let Some(created_from) = filter.created_from {
+            query = query.filter(operation::Column::CreatedAt.gte(created_from));
+        }
+        if let Some(created_to) = filter.created_to {
+            query = query.filter(operation::Column::CreatedAt.lte(created_to));
+        }
+        if let Some(cosigner_idx) = filter.unprocessed_by {
+            query = query.filter(
+                operation::Column::Idx.in_subquery(
+                    Query::select()
+                        .column(cosigner_op_status::Column::OperationIdx)
+                        .from(CosignerOpStatus)
+                        .and_where(cosigner_op_status::Column::CosignerIdx.eq(cosigner_idx))
+                        .and_where(cosigner_op_status::Column::ProcessedAt.is_null())
+                        .to_owned(),
+                ),
+            );
+        }
+        Ok(query
+            .order_by_asc(operation::Column::Idx)
+            .limit(limit)
+            .all(self.get_connection())
+            .await?)
+    }
+
+    pub(crate) async fn iter_outflows_approved_after(
+        &self,
+        timestamp: i64,
+    ) -> Result<Vec<outflow::Model>, APIError> {
+        Ok(Outflow::find()
+            .filter(outflow::Column::ApprovedAt.gt(timestamp))
+            .order_by_asc(outflow::Column::Idx)
+            .all(self.get_connection())
+            .await?)
+    }
+
+    pub(crate) async fn iter_webhook_deliveries(
+        &self,
+        status: Option<WebhookDeliveryStatus>,
+        after_idx: Option<i32>,
+        limit: Option<u64>,
+    ) -> Result<Vec<webhook_delivery::Model>, APIError> {
+        let mut query = WebhookDelivery::find();
+        if let Some(status) = status {
+            query = query.filter(webhook_delivery::Column::Status.eq(status));
+        }
+        if let Some(after_idx) = after_idx {
+            query = query.filter(webhook_delivery::Column::Idx.gt(after_idx));
+        }
+        Ok(query
+            .order_by_asc(webhook_delivery::Column::Idx)
+            .limit(limit)
+            .all(self.get_connection())
+            .await?)
+    }
+
     pub(crate) async fn has_pending_operation(&self) -> Result<bool, APIError> {
         Ok(Operation::find()
-            .filter(operation::Column::Status.eq(OperationStatus::Pending))
+            .filter(operation::Column::Status.is_in([
+                OperationStatus::Pending,
+                OperationStatus::ApprovedPendingDelay,
+            ]))
+            .one(self.get_connection())
+            .await?
+            .is_some())
+    }
+
+    pub(crate) async fn has_revoked_token(
+        &self,
+        revocation_ids: Vec<String>,
+    ) -> Result<bool, APIError> {
+        Ok(RevokedToken::find()
+            .filter(revoked_token::Column::RevocationId.is_in(revocation_ids))
             .one(self.get_connection())
             .await?
             .is_some())
@@ -6,6 +6,8 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::startup::MAX_PENDING_TIMEOUT;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct APIErrorResponse {
     pub(crate) error: String,
@@ -16,6 +18,9 @@ pub(crate) struct APIErrorResponse {
 /// The error variants returned by APIs
 #[derive(Debug, thiserror::Error)]
 pub enum APIError {
+    #[error("Cannot cancel operation: {0}")]
+    CannotCancelOperation(String),
+
     #[error("Cannot mark operation as processed: {0}")]
     CannotMarkOperationProcessed(String),
 
@@ -25,6 +30,15 @@ pub enum APIError {
     #[error("Cannot respond to operation: {0}")]
     CannotRespondToOperation(String),
 
+    #[error("Cannot revoke token: {0}")]
+    CannotRevokeToken(String),
+
+    #[error("Cannot update chain status: {0}")]
+    CannotUpdateChainStatus(String),
+
+    #[error("Cannot veto operation: {0}")]
+    CannotVetoOperation(String),
+
     #[error("Database error: {0}")]
     Database(#[from] sea_orm::DbErr),
 
@@ -37,15 +51,30 @@ pub enum APIError {
     #[error("Invalid operation type: {0}")]
     InvalidOperationType(u8),
 
+    #[error("Invalid PSBT: {0}")]
+    InvalidPsbt(String),
+
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+
     #[error("IO error: {0}")]
     IO(#[from] std::io::Error),
 
+    #[error("Network mismatch: {0}")]
+    NetworkMismatch(String),
+
     #[error("Operation not found")]
     OperationNotFound,
 
+    #[error("Operation rejected by policy: {0}")]
+    RejectedByPolicy(String),
+
+    #[error("Spending limit exceeded: {0}")]
+    SpendingLimitExceeded(String),
+
     #[error("Unexpected error: {0}")]
     Unexpected(String),
 }
@@ -86,13 +115,22 @@ impl IntoResponse for APIError {
             APIError::FileNotFound
             | APIError::InvalidCount
             | APIError::InvalidOperationType(_)
+            | APIError::InvalidPsbt(_)
             | APIError::InvalidRequest(_)
+            | APIError::InvalidToken(_)
+            | APIError::NetworkMismatch(_)
             | APIError::OperationNotFound => {
                 (StatusCode::BAD_REQUEST, self.to_string(), self.name())
             }
-            APIError::CannotMarkOperationProcessed(_)
+            APIError::CannotCancelOperation(_)
+            | APIError::CannotMarkOperationProcessed(_)
             | APIError::CannotPostNewOperation(_)
-            | APIError::CannotRespondToOperation(_) => {
+            | APIError::CannotRespondToOperation(_)
+            | APIError::CannotRevokeToken(_)
+            | APIError::CannotUpdateChainStatus(_)
+            | APIError::CannotVetoOperation(_)
+            | APIError::RejectedByPolicy(_)
+            | APIError::SpendingLimitExceeded(_) => {
                 (StatusCode::FORBIDDEN, self.to_string(), self.name())
             }
         };
@@ -120,6 +158,9 @@ pub enum AppError {
     #[error("Cannot change cosigners")]
     CannotChangeCosigners,
 
+    #[error("Cannot change network")]
+    CannotChangeNetwork,
+
     #[error("Config error: {0}")]
     Config(#[from] confy::ConfyError),
 
@@ -129,18 +170,51 @@ pub enum AppError {
     #[error("Inconsistent state: {0}")]
     InconsistentState(String),
 
+    #[error("Invalid approval delay: {0}")]
+    InvalidApprovalDelay(String),
+
+    #[error("Invalid auto-approved operation types: {0}")]
+    InvalidAutoApprovedOps(String),
+
+    #[error("Invalid chain source: {0}")]
+    InvalidChainSource(String),
+
     #[error("Invalid cosigner number: {0}")]
     InvalidCosignerNumber(usize),
 
+    #[error("Invalid cosigner role: {0}")]
+    InvalidCosignerRole(String),
+
+    #[error("Invalid cosigner weight: {0}")]
+    InvalidCosignerWeight(String),
+
+    #[error("Invalid cosigner xPub: {0}")]
+    InvalidCosignerXpub(String),
+
+    #[error(
+        "Invalid pending timeout: must be a positive number of seconds, up to {}",
+        MAX_PENDING_TIMEOUT
+    )]
+    InvalidPendingTimeout,
+
+    #[error("Invalid policy: {0}")]
+    InvalidPolicy(String),
+
     #[error("Invalid rgb-lib version: {0}")]
     InvalidRgbLibVersion(String),
 
     #[error("The provided root public key is invalid")]
     InvalidRootKey,
 
+    #[error("Invalid spending limit: {0}")]
+    InvalidSpendingLimit(String),
+
     #[error("Invalid threshold: {0}")]
     InvalidThreshold(String),
 
+    #[error("Invalid webhook: {0}")]
+    InvalidWebhook(String),
+
     #[error("IO error: {0}")]
     IO(#[from] std::io::Error),
 
@@ -264,6 +338,15 @@ mod tests {
         assert_eq!(body.name, "InvalidRequest");
         assert!(body.error.contains("invalid json"));
 
+        // InvalidToken
+        let err = APIError::InvalidToken(s!("not base64"));
+        let response = err.into_response();
+        let (status, body) = extract_response_body(response).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, 400);
+        assert_eq!(body.name, "InvalidToken");
+        assert_eq!(body.error, "Invalid token: not base64");
+
         // OperationNotFound
         let err = APIError::OperationNotFound;
         let response = err.into_response();
@@ -276,6 +359,15 @@ mod tests {
 
     #[tokio::test]
     async fn test_api_error_into_response_forbidden() {
+        // CannotCancelOperation
+        let err = APIError::CannotCancelOperation(s!("not the initiator"));
+        let response = err.into_response();
+        let (status, body) = extract_response_body(response).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.code, 403);
+        assert_eq!(body.name, "CannotCancelOperation");
+        assert!(body.error.contains("not the initiator"));
+
         // CannotMarkOperationProcessed
         let err = APIError::CannotMarkOperationProcessed(s!("not allowed"));
         let response = err.into_response();
@@ -302,6 +394,42 @@ mod tests {
         assert_eq!(body.code, 403);
         assert_eq!(body.name, "CannotRespondToOperation");
         assert!(body.error.contains("already responded"));
+
+        // CannotRevokeToken
+        let err = APIError::CannotRevokeToken(s!("already revoked"));
+        let response = err.into_response();
+        let (status, body) = extract_response_body(response).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.code, 403);
+        assert_eq!(body.name, "CannotRevokeToken");
+        assert!(body.error.contains("already revoked"));
+
+        // CannotUpdateChainStatus
+        let err = APIError::CannotUpdateChainStatus(s!("already confirmed"));
+        let response = err.into_response();
+        let (status, body) = extract_response_body(response).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.code, 403);
+        assert_eq!(body.name, "CannotUpdateChainStatus");
+        assert!(body.error.contains("already confirmed"));
+
+        // CannotVetoOperation
+        let err = APIError::CannotVetoOperation(s!("not in its approval delay"));
+        let response = err.into_response();
+        let (status, body) = extract_response_body(response).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.code, 403);
+        assert_eq!(body.name, "CannotVetoOperation");
+        assert!(body.error.contains("not in its approval delay"));
+
+        // SpendingLimitExceeded
+        let err = APIError::SpendingLimitExceeded(s!("over the limit"));
+        let response = err.into_response();
+        let (status, body) = extract_response_body(response).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.code, 403);
+        assert_eq!(body.name, "SpendingLimitExceeded");
+        assert_eq!(body.error, "Spending limit exceeded: over the limit");
     }
 
     #[tokio::test]
@@ -326,7 +454,12 @@ mod tests {
             APIError::InvalidRequest(s!("test")).name(),
             "InvalidRequest"
         );
+        assert_eq!(APIError::InvalidToken(s!("test")).name(), "InvalidToken");
         assert_eq!(APIError::OperationNotFound.name(), "OperationNotFound");
+        assert_eq!(
+            APIError::CannotCancelOperation(s!("test")).name(),
+            "CannotCancelOperation"
+        );
         assert_eq!(
             APIError::CannotMarkOperationProcessed(s!("test")).name(),
             "CannotMarkOperationProcessed"
@@ -339,6 +472,18 @@ mod tests {
             APIError::CannotRespondToOperation(s!("test")).name(),
             "CannotRespondToOperation"
         );
+        assert_eq!(
+            APIError::CannotRevokeToken(s!("test")).name(),
+            "CannotRevokeToken"
+        );
+        assert_eq!(
+            APIError::CannotUpdateChainStatus(s!("test")).name(),
+            "CannotUpdateChainStatus"
+        );
+        assert_eq!(
+            APIError::CannotVetoOperation(s!("test")).name(),
+            "CannotVetoOperation"
+        );
         assert_eq!(APIError::Unexpected(s!("test")).name(), "Unexpected");
     }
 }
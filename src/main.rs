@@ -1,8 +1,12 @@
 mod auth;
+mod chain;
 mod database;
 mod error;
+mod policy;
+mod psbt;
 mod routes;
 mod startup;
+mod tasks;
 mod utils;
 
 #[cfg(test)]
@@ -35,8 +39,10 @@ use crate::{
     auth::conditional_auth_middleware,
     error::AppError,
     routes::{
-        bump_address_indices, get_current_address_indices, get_file, get_last_processed_op_idx,
-        get_operation_by_idx, info, mark_operation_processed, post_operation, respond_to_operation,
+        bump_address_indices, cancel_operation, events, get_current_address_indices, get_file,
+        get_last_processed_op_idx, get_operation_by_idx, info, list_operations,
+        list_webhook_deliveries, mark_operation_processed, post_operation, respond_to_operation,
+        revoke_token, sync_operations, update_chain_status, veto_operation,
     },
     startup::{AppParams, AppState, LOGS_DIR, parse_startup_args_and_config, start_daemon},
 };
@@ -90,6 +96,8 @@ pub(crate) async fn app(app_params: AppParams) -> Result<(Router, Arc<AppState>)
         // all routes before this will have the default body limit disabled
         .layer(DefaultBodyLimit::disable())
         .route("/bumpaddressindices", post(bump_address_indices))
+        .route("/canceloperation", post(cancel_operation))
+        .route("/events", get(events))
         .route(
             "/getcurrentaddressindices",
             get(get_current_address_indices),
@@ -98,8 +106,14 @@ pub(crate) async fn app(app_params: AppParams) -> Result<(Router, Arc<AppState>)
         .route("/getlastprocessedopidx", get(get_last_processed_op_idx))
         .route("/getoperationbyidx", post(get_operation_by_idx))
         .route("/info", get(info))
+        .route("/listoperations", post(list_operations))
+        .route("/listwebhookdeliveries", post(list_webhook_deliveries))
         .route("/markoperationprocessed", post(mark_operation_processed))
         .route("/respondtooperation", post(respond_to_operation))
+        .route("/revoketoken", post(revoke_token))
+        .route("/syncoperations", post(sync_operations))
+        .route("/updatechainstatus", post(update_chain_status))
+        .route("/vetooperation", post(veto_operation))
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(|request: &Request<_>| {
@@ -156,6 +170,9 @@ async fn shutdown_signal(app_state: Arc<AppState>) {
     }
 
     tracing::info!("Received a shutdown signal");
+
+    // stop background tasks and close open event streams
+    cancel_token.cancel();
 }
 
 // workaround for https://github.com/tokio-rs/tracing/issues/1372
@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+
+use amplify::s;
+use bitcoin::ScriptBuf;
+
+use crate::{
+    error::APIError,
+    routes::{OperationType, PsbtSummary, SpendingLimitUsage},
+    startup::{PolicyConfig, SpendingLimitConfig},
+};
+
+impl PolicyConfig {
+    // whether the policy applies to the operation type, allowlist updates have no PSBT to evaluate
+    fn applies_to(&self, operation_type: OperationType) -> bool {
+        operation_type != OperationType::UpdateAllowlist
+            && (self.operation_types.is_empty() || self.operation_types.contains(&operation_type))
+    }
+
+    // whether all the policy conditions are met, unknown values meet a condition so that policies
+    // fail closed
+    fn matches(&self, psbt_summary: &PsbtSummary, address_allowlist: &HashSet<String>) -> bool {
+        if let Some(amount) = self.sent_amount_above
+            && psbt_summary.sent_amount() <= amount
+        {
+            return false;
+        }
+        if self.non_allowlisted_outputs
+            && find_non_allowlisted_output(psbt_summary, address_allowlist).is_none()
+        {
+            return false;
+        }
+        if let Some(fee_rate) = self.fee_rate_above
+            && psbt_summary
+                .fee_rate_sat_per_vb
+                .is_some_and(|summary_fee_rate| summary_fee_rate <= fee_rate)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+impl PsbtSummary {
+    /// Amount (in sats) sent to non-change outputs
+    pub(crate) fn sent_amount(&self) -> u64 {
+        self.outputs
+            .iter()
+            .filter(|o| !o.is_change)
+            .fold(0, |sum, o| sum.saturating_add(o.amount_sat))
+    }
+
+    /// Amount (in sats) leaving the multisig, unknown if the fee is
+    pub(crate) fn outflow(&self) -> Option<u64> {
+        self.fee_sat
+            .map(|fee_sat| self.sent_amount().saturating_add(fee_sat))
+    }
+}
+
+/// Find the first output paying outside the allowlist, ignoring change and OP_RETURN outputs
+pub(crate) fn find_non_allowlisted_output(
+    psbt_summary: &PsbtSummary,
+    address_allowlist: &HashSet<String>,
+) -> Option<usize> {
+    psbt_summary.outputs.iter().position(|o| {
+        let is_op_return = ScriptBuf::from_hex(&o.script_pubkey).is_ok_and(|s| s.is_op_return());
+        !o.is_change
+            && !is_op_return
+            && o.address
+                .as_ref()
+                .is_none_or(|a| !address_allowlist.contains(a))
+    })
+}
+
+/// Evaluate the policies on an operation, returning its effective threshold
+pub(crate) fn apply_policies(
+    policies: &[PolicyConfig],
+    address_allowlist: &HashSet<String>,
+    operation_type: OperationType,
+    psbt_summary: Option<&PsbtSummary>,
+    threshold: u8,
+) -> Result<u8, APIError> {
+    let mut effective_threshold = threshold;
+    for (idx, policy) in policies.iter().enumerate() {
+        if !policy.applies_to(operation_type) {
+            continue;
+        }
+        let Some(psbt_summary) = psbt_summary else {
+            return Err(APIError::RejectedByPolicy(format!(
+                "a PSBT is required by policy #{}",
+                idx + 1
+            )));
+        };
+        if !policy.matches(psbt_summary, address_allowlist) {
+            continue;
+        }
+        if policy.reject {
+            return Err(APIError::RejectedByPolicy(format!(
+                "operation matches policy #{}",
+                idx + 1
+            )));
+        }
+        if let Some(policy_threshold) = policy.threshold {
+            effective_threshold = effective_threshold.max(policy_threshold);
+        }
+    }
+    Ok(effective_threshold)
+}
+
+/// Check an operation outflow against the spending limits, returning its effective threshold. An
+/// unknown outflow is considered to exceed all of them.
+pub(crate) fn apply_spending_limits(
+    spending_limits: &[SpendingLimitConfig],
+    usage: &[SpendingLimitUsage],
+    outflow: Option<u64>,
+    threshold: u8,
+) -> Result<u8, APIError> {
+    let mut effective_threshold = threshold;
+    for (idx, (limit, usage)) in spending_limits.iter().zip(usage).enumerate() {
+        if outflow.is_some_and(|outflow| outflow <= usage.remaining_sat) {
+            continue;
+        }
+        if limit.reject {
+            let outflow = match outflow {
+                Some(outflow) => format!("outflow of {outflow} sats"),
+                None => s!("unknown outflow"),
+            };
+            return Err(APIError::SpendingLimitExceeded(format!(
+                "{outflow} exceeds the remaining allowance of {} sats of spending limit #{}",
+                usage.remaining_sat,
+                idx + 1
+            )));
+        }
+        if let Some(limit_threshold) = limit.threshold {
+            effective_threshold = effective_threshold.max(limit_threshold);
+        }
+    }
+    Ok(effective_threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::{PsbtOutputSummary, SpendingLimitWindow};
+
+    use super::*;
+
+    fn summary(outputs: Vec<(&str, u64, bool)>, fee_rate: Option<f64>) -> PsbtSummary {
+        PsbtSummary {
+            inputs: vec![],
+            outputs: outputs
+                .into_iter()
+                .map(|(address, amount_sat, is_change)| PsbtOutputSummary {
+                    address: Some(address.to_string()),
+                    script_pubkey: s!(""),
+                    amount_sat,
+                    is_change,
+                })
+                .collect(),
+            fee_sat: None,
+            fee_rate_sat_per_vb: fee_rate,
+        }
+    }
+
+    #[test]
+    fn test_apply_policies() {
+        let allowlist = HashSet::from([s!("allowed")]);
+        let policies = vec![
+            PolicyConfig {
+                operation_types: vec![OperationType::SendBtc],
+                sent_amount_above: Some(100_000_000),
+                threshold: Some(3),
+                ..Default::default()
+            },
+            PolicyConfig {
+                non_allowlisted_outputs: true,
+                threshold: Some(4),
+                ..Default::default()
+            },
+            PolicyConfig {
+                fee_rate_above: Some(100.0),
+                reject: true,
+                ..Default::default()
+            },
+        ];
+        let apply = |operation_type, summary: Option<&PsbtSummary>| {
+            apply_policies(&policies, &allowlist, operation_type, summary, 2)
+        };
+
+        // no policy matched, change outputs don't count as sent
+        let below_amount = summary(
+            vec![("allowed", 100_000_000, false), ("other", 1, true)],
+            Some(1.0),
+        );
+        assert_eq!(
+            apply(OperationType::SendBtc, Some(&below_amount)).unwrap(),
+            2
+        );
+
+        // a PSBT is required, but not for allowlist updates
+        let err = apply(OperationType::SendBtc, None).unwrap_err();
+        assert!(matches!(err, APIError::RejectedByPolicy(msg) if msg.contains("policy #1")));
+        assert_eq!(apply(OperationType::UpdateAllowlist, None).unwrap(), 2);
+
+        // amount policy only applies to its operation types
+        let above_amount = summary(vec![("allowed", 100_000_001, false)], Some(1.0));
+        assert_eq!(
+            apply(OperationType::SendBtc, Some(&above_amount)).unwrap(),
+            3
+        );
+        assert_eq!(
+            apply(OperationType::SendRgb, Some(&above_amount)).unwrap(),
+            2
+        );
+
+        // the highest threshold among the matched policies wins
+        let non_allowlisted = summary(vec![("other", 100_000_001, false)], Some(1.0));
+        assert_eq!(
+            apply(OperationType::SendBtc, Some(&non_allowlisted)).unwrap(),
+            4
+        );
+
+        // an unknown fee rate meets the condition
+        let unknown_fee_rate = summary(vec![("allowed", 1, false)], None);
+        let err = apply(OperationType::SendBtc, Some(&unknown_fee_rate)).unwrap_err();
+        assert!(matches!(err, APIError::RejectedByPolicy(msg) if msg.contains("policy #3")));
+
+        // rejection
+        let high_fee_rate = summary(vec![("allowed", 1, false)], Some(100.1));
+        let err = apply(OperationType::CreateUtxos, Some(&high_fee_rate)).unwrap_err();
+        assert!(matches!(err, APIError::RejectedByPolicy(msg) if msg.contains("policy #3")));
+    }
+
+    #[test]
+    fn test_apply_spending_limits() {
+        let spending_limits = vec![
+            SpendingLimitConfig {
+                window: SpendingLimitWindow::Daily,
+                amount_sat: 1000,
+                threshold: Some(4),
+                ..Default::default()
+            },
+            SpendingLimitConfig {
+                window: SpendingLimitWindow::Weekly,
+                amount_sat: 5000,
+                reject: true,
+                ..Default::default()
+            },
+        ];
+        let usage = |daily_used: u64, weekly_used: u64| {
+            spending_limits
+                .iter()
+                .zip([daily_used, weekly_used])
+                .map(|(limit, used_sat)| SpendingLimitUsage {
+                    window: limit.window,
+                    limit_sat: limit.amount_sat,
+                    used_sat,
+                    remaining_sat: limit.amount_sat.saturating_sub(used_sat),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // within all the remaining allowances
+        assert_eq!(
+            apply_spending_limits(&spending_limits, &usage(0, 0), Some(1000), 2).unwrap(),
+            2
+        );
+        assert_eq!(
+            apply_spending_limits(&spending_limits, &usage(1000, 4000), Some(0), 2).unwrap(),
+            2
+        );
+
+        // exceeding the daily allowance raises the threshold
+        assert_eq!(
+            apply_spending_limits(&spending_limits, &usage(500, 500), Some(501), 2).unwrap(),
+            4
+        );
+
+        // exceeding the weekly allowance rejects the operation
+        let err =
+            apply_spending_limits(&spending_limits, &usage(0, 4500), Some(501), 2).unwrap_err();
+        assert!(matches!(err, APIError::SpendingLimitExceeded(msg) if msg.contains("limit #2")));
+
+        // an unknown outflow exceeds all the allowances
+        let err = apply_spending_limits(&spending_limits, &usage(0, 0), None, 2).unwrap_err();
+        assert!(
+            matches!(err, APIError::SpendingLimitExceeded(msg) if msg.starts_with("unknown outflow"))
+        );
+        assert_eq!(
+            apply_spending_limits(&spending_limits[..1], &usage(0, 0), None, 2).unwrap(),
+            4
+        );
+    }
+}
@@ -0,0 +1,454 @@
+use std::path::Path;
+
+use amplify::s;
+use bitcoin::{
+    Address, Amount, Psbt, Script, ScriptBuf, TxOut, Weight,
+    bip32::{ChildNumber, DerivationPath, Xpub},
+    key::TapTweak,
+    psbt::{Error as PsbtError, Input as PsbtInput, Output as PsbtOutput},
+    script::Instruction,
+    secp256k1::{Message, Parity, PublicKey, Secp256k1, Verification},
+    sighash::{Prevouts, SighashCache},
+};
+use miniscript::{Descriptor, Miniscript, psbt::PsbtExt};
+
+use crate::{
+    error::APIError,
+    routes::{BitcoinNetwork, PsbtInputSummary, PsbtOutputSummary, PsbtSummary},
+};
+
+// BIP44-style purposes, whose derivation paths have the coin type as second level
+const COIN_TYPE_PURPOSES: [u32; 5] = [44, 48, 49, 84, 86];
+
+/// Check that a PSBT belongs to the bridge network.
+///
+/// xPub versions and coin types only tell mainnet apart from the test networks, which share
+/// them, so a PSBT built for another test network can't be detected.
+pub(crate) fn check_psbt_network(psbt: &Psbt, network: BitcoinNetwork) -> Result<(), APIError> {
+    for xpub in psbt.xpub.keys() {
+        if xpub.network != network.network_kind() {
+            return Err(APIError::NetworkMismatch(format!(
+                "PSBT xPub '{xpub}' does not belong to the {network:?} network"
+            )));
+        }
+    }
+    let key_sources = psbt
+        .xpub
+        .values()
+        .chain(psbt.inputs.iter().flat_map(|i| {
+            i.bip32_derivation
+                .values()
+                .chain(i.tap_key_origins.values().map(|(_, s)| s))
+        }))
+        .chain(psbt.outputs.iter().flat_map(|o| {
+            o.bip32_derivation
+                .values()
+                .chain(o.tap_key_origins.values().map(|(_, s)| s))
+        }));
+    let mut has_key_sources = false;
+    for (_, path) in key_sources {
+        has_key_sources = true;
+        // only the mainnet (0) and testnet (1) coin types are known to belong to a network
+        if let [
+            ChildNumber::Hardened { index: purpose },
+            ChildNumber::Hardened { index: coin_type },
+            ..,
+        ] = path.as_ref()
+            && COIN_TYPE_PURPOSES.contains(purpose)
+            && [0, 1].contains(coin_type)
+            && *coin_type != network.coin_type()
+        {
+            return Err(APIError::NetworkMismatch(format!(
+                "PSBT derivation path '{path}' does not belong to the {network:?} network"
+            )));
+        }
+    }
+    if !has_key_sources {
+        return Err(APIError::NetworkMismatch(s!(
+            "PSBT has no xPubs or derivation paths to check its network against"
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_psbt_signatures(
+    psbt: &Psbt,
+    initiator_psbt: Option<&Psbt>,
+    xpub: &Xpub,
+) -> Result<(), APIError> {
+    let secp = Secp256k1::verification_only();
+    let mut cache = SighashCache::new(&psbt.unsigned_tx);
+    let prevouts: Option<Vec<TxOut>> = (0..psbt.inputs.len())
+        .map(|i| psbt.spend_utxo(i).ok().cloned())
+        .collect();
+    let foreign_signature = |idx| {
+        APIError::InvalidPsbt(format!(
+            "input {idx} has a signature not belonging to the responding cosigner"
+        ))
+    };
+    let invalid_signature =
+        |idx| APIError::InvalidPsbt(format!("input {idx} has an invalid signature"));
+    let sighash_error =
+        |idx, e| APIError::InvalidPsbt(format!("cannot compute sighash for input {idx}: {e}"));
+
+    let mut num_signatures = 0;
+    for (idx, input) in psbt.inputs.iter().enumerate() {
+        // signatures already in the initiator's PSBT are not the responding cosigner's ones
+        let initiator_input = initiator_psbt.and_then(|p| p.inputs.get(idx));
+
+        for (key, signature) in &input.partial_sigs {
+            if initiator_input.is_some_and(|i| i.partial_sigs.get(key) == Some(signature)) {
+                continue;
+            }
+            let derived_key = input
+                .bip32_derivation
+                .get(&key.inner)
+                .and_then(|(_, path)| derive_key(&secp, xpub, path));
+            if derived_key != Some(key.inner) {
+                return Err(foreign_signature(idx));
+            }
+            let (msg, sighash_type) = psbt
+                .sighash_ecdsa(idx, &mut cache)
+                .map_err(|e| sighash_error(idx, e.to_string()))?;
+            if signature.sighash_type != sighash_type
+                || secp
+                    .verify_ecdsa(&msg, &signature.signature, &key.inner)
+                    .is_err()
+            {
+                return Err(invalid_signature(idx));
+            }
+            num_signatures += 1;
+        }
+
+        for ((key, leaf_hash), signature) in &input.tap_script_sigs {
+            if initiator_input
+                .is_some_and(|i| i.tap_script_sigs.get(&(*key, *leaf_hash)) == Some(signature))
+            {
+                continue;
+            }
+            let derived_key = input
+                .tap_key_origins
+                .get(key)
+                .and_then(|(_, (_, path))| derive_key(&secp, xpub, path));
+            if derived_key.map(|k| k.x_only_public_key().0) != Some(*key) {
+                return Err(foreign_signature(idx));
+            }
+            let prevouts = prevouts
+                .as_ref()
+                .ok_or(sighash_error(idx, s!("missing spent outputs")))?;
+            let sighash = cache
+                .taproot_script_spend_signature_hash(
+                    idx,
+                    &Prevouts::All(prevouts),
+                    *leaf_hash,
+                    signature.sighash_type,
+                )
+                .map_err(|e| sighash_error(idx, e.to_string()))?;
+            if secp
+                .verify_schnorr(&signature.signature, &Message::from(sighash), key)
+                .is_err()
+            {
+                return Err(invalid_signature(idx));
+            }
+            num_signatures += 1;
+        }
+
+        if let Some(signature) = &input.tap_key_sig
+            && initiator_input.is_none_or(|i| i.tap_key_sig.as_ref() != Some(signature))
+        {
+            let Some(internal_key) = input.tap_internal_key else {
+                return Err(foreign_signature(idx));
+            };
+            let derived_key = input
+                .tap_key_origins
+                .get(&internal_key)
+                .and_then(|(_, (_, path))| derive_key(&secp, xpub, path));
+            if derived_key.map(|k| k.x_only_public_key().0) != Some(internal_key) {
+                return Err(foreign_signature(idx));
+            }
+            let prevouts = prevouts
+                .as_ref()
+                .ok_or(sighash_error(idx, s!("missing spent outputs")))?;
+            let sighash = cache
+                .taproot_key_spend_signature_hash(
+                    idx,
+                    &Prevouts::All(prevouts),
+                    signature.sighash_type,
+                )
+                .map_err(|e| sighash_error(idx, e.to_string()))?;
+            let (output_key, _) = internal_key.tap_tweak(&secp, input.tap_merkle_root);
+            if secp
+                .verify_schnorr(
+                    &signature.signature,
+                    &Message::from(sighash),
+                    &output_key.to_x_only_public_key(),
+                )
+                .is_err()
+            {
+                return Err(invalid_signature(idx));
+            }
+            num_signatures += 1;
+        }
+    }
+    if num_signatures == 0 {
+        return Err(APIError::InvalidPsbt(s!(
+            "no signature from the responding cosigner"
+        )));
+    }
+    Ok(())
+}
+
+// derives the key with the given origin path from the xPub, if the path goes through it
+fn derive_key<C: Verification>(
+    secp: &Secp256k1<C>,
+    xpub: &Xpub,
+    path: &DerivationPath,
+) -> Option<PublicKey> {
+    let depth = xpub.depth as usize;
+    let path = path.as_ref();
+    if depth > 0 && path.get(depth - 1) != Some(&xpub.child_number) {
+        return None;
+    }
+    let children = path.get(depth..)?;
+    xpub.derive_pub(secp, &children).ok().map(|k| k.public_key)
+}
+
+// combines the given PSBTs, finalizing the result if it has enough signatures, if there are any
+pub(crate) fn combine_psbts(psbts: Vec<Psbt>) -> Result<Option<Psbt>, APIError> {
+    let mut psbts = psbts.into_iter();
+    let Some(mut combined) = psbts.next() else {
+        return Ok(None);
+    };
+    for psbt in psbts {
+        combined
+            .combine(psbt)
+            .map_err(|e| APIError::InvalidPsbt(format!("cannot combine PSBTs: {e}")))?;
+    }
+    let mut finalized = combined.clone();
+    match finalized.finalize_mut(&Secp256k1::verification_only()) {
+        Ok(()) => Ok(Some(finalized)),
+        Err(errors) => {
+            tracing::debug!("Combined PSBT cannot be finalized: {errors:?}");
+            Ok(Some(combined))
+        }
+    }
+}
+
+pub(crate) fn summarize_psbt(psbt: &Psbt, xpubs: &[Xpub], network: BitcoinNetwork) -> PsbtSummary {
+    let secp = Secp256k1::verification_only();
+    let inputs: Vec<_> = psbt
+        .unsigned_tx
+        .input
+        .iter()
+        .enumerate()
+        .map(|(idx, txin)| PsbtInputSummary {
+            outpoint: txin.previous_output.to_string(),
+            amount_sat: psbt.spend_utxo(idx).ok().map(|utxo| utxo.value.to_sat()),
+        })
+        .collect();
+    let outputs: Vec<_> = psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .zip(&psbt.outputs)
+        .map(|(txout, output)| PsbtOutputSummary {
+            address: Address::from_script(&txout.script_pubkey, network.network())
+                .ok()
+                .map(|a| a.to_string()),
+            script_pubkey: txout.script_pubkey.to_hex_string(),
+            amount_sat: txout.value.to_sat(),
+            is_change: is_change_output(&secp, output, &txout.script_pubkey, xpubs),
+        })
+        .collect();
+
+    // fee is known only if all the spent amounts are, and they don't overflow
+    let amount_in = inputs.iter().try_fold(Amount::ZERO, |sum, i| {
+        sum.checked_add(Amount::from_sat(i.amount_sat?))
+    });
+    let amount_out = output_amount(psbt);
+    let fee = amount_in
+        .zip(amount_out)
+        .and_then(|(amount_in, amount_out)| amount_in.checked_sub(amount_out));
+    let fee_rate_sat_per_vb = fee
+        .zip(estimate_signed_weight(psbt))
+        .map(|(fee, weight)| fee.to_sat() as f64 / weight.to_vbytes_ceil() as f64);
+
+    PsbtSummary {
+        inputs,
+        outputs,
+        fee_sat: fee.map(|f| f.to_sat()),
+        fee_rate_sat_per_vb,
+    }
+}
+
+// total amount of the transaction outputs, if it doesn't overflow
+fn output_amount(psbt: &Psbt) -> Option<Amount> {
+    psbt.unsigned_tx
+        .output
+        .iter()
+        .try_fold(Amount::ZERO, |sum, o| sum.checked_add(o.value))
+}
+
+// an output is change if it pays to a script made only of keys derived from the cosigner xPubs,
+// with at least one key for each of them
+fn is_change_output<C: Verification>(
+    secp: &Secp256k1<C>,
+    output: &PsbtOutput,
+    script_pubkey: &Script,
+    xpubs: &[Xpub],
+) -> bool {
+    // keys in the output's scripts, serialized as they appear there
+    let (script_keys, key_origins): (Vec<Vec<u8>>, Vec<_>) = if let Some(internal_key) =
+        output.tap_internal_key
+    {
+        let merkle_root = output.tap_tree.as_ref().map(|t| t.root_hash());
+        if *script_pubkey != ScriptBuf::new_p2tr(secp, internal_key, merkle_root) {
+            return false;
+        }
+        let mut script_keys = vec![internal_key.serialize().to_vec()];
+        for leaf in output.tap_tree.iter().flat_map(|t| t.script_leaves()) {
+            script_keys.extend(pushed_keys(leaf.script(), 32));
+        }
+        let key_origins = output
+            .tap_key_origins
+            .iter()
+            .map(|(key, (_, (_, path)))| (key.serialize().to_vec(), path))
+            .collect();
+        (script_keys, key_origins)
+    } else {
+        let script = match (&output.redeem_script, &output.witness_script) {
+            (None, Some(ws)) if *script_pubkey == ScriptBuf::new_p2wsh(&ws.wscript_hash()) => ws,
+            (Some(rs), Some(ws))
+                if *rs == ScriptBuf::new_p2wsh(&ws.wscript_hash())
+                    && *script_pubkey == ScriptBuf::new_p2sh(&rs.script_hash()) =>
+            {
+                ws
+            }
+            (Some(rs), None) if *script_pubkey == ScriptBuf::new_p2sh(&rs.script_hash()) => rs,
+            _ => return false,
+        };
+        let key_origins = output
+            .bip32_derivation
+            .iter()
+            .map(|(key, (_, path))| (key.serialize().to_vec(), path))
+            .collect();
+        (pushed_keys(script, 33).collect(), key_origins)
+    };
+    if script_keys.is_empty() {
+        return false;
+    }
+
+    // all script keys must be derived from one of the xPubs
+    let mut derived_from = vec![false; xpubs.len()];
+    for script_key in &script_keys {
+        let Some((_, path)) = key_origins.iter().find(|(key, _)| key == script_key) else {
+            return false;
+        };
+        let Some(xpub_idx) = xpubs.iter().position(|xpub| {
+            derive_key(secp, xpub, path).is_some_and(|derived| {
+                let derived = if script_key.len() == 32 {
+                    derived.x_only_public_key().0.serialize().to_vec()
+                } else {
+                    derived.serialize().to_vec()
+                };
+                derived == *script_key
+            })
+        }) else {
+            return false;
+        };
+        derived_from[xpub_idx] = true;
+    }
+    derived_from.into_iter().all(|d| d)
+}
+
+// pushes in the script having the given length, which could be keys
+fn pushed_keys(script: &Script, len: usize) -> impl Iterator<Item = Vec<u8>> + '_ {
+    script.instructions().filter_map(move |i| match i {
+        Ok(Instruction::PushBytes(bytes)) if bytes.len() == len => Some(bytes.as_bytes().to_vec()),
+        _ => None,
+    })
+}
+
+// estimates the weight of the transaction once signed, if all the inputs can be satisfied
+fn estimate_signed_weight(psbt: &Psbt) -> Option<Weight> {
+    let mut weight = psbt.unsigned_tx.weight();
+    let mut is_segwit = false;
+    for (idx, input) in psbt.inputs.iter().enumerate() {
+        let script_pubkey = &psbt.spend_utxo(idx).ok()?.script_pubkey;
+        let satisfaction_weight = input_satisfaction_weight(input, script_pubkey)?;
+        if script_pubkey.is_witness_program()
+            || input.witness_script.is_some()
+            || input.final_script_witness.is_some()
+        {
+            is_segwit = true;
+        }
+        weight += satisfaction_weight;
+    }
+    if is_segwit {
+        // segwit marker and flag, plus the witness element count of each input
+        weight += Weight::from_wu(2 + psbt.inputs.len() as u64);
+    }
+    Some(weight)
+}
+
+fn input_satisfaction_weight(input: &PsbtInput, script_pubkey: &Script) -> Option<Weight> {
+    if input.final_script_sig.is_some() || input.final_script_witness.is_some() {
+        let script_sig_len = input.final_script_sig.as_ref().map_or(0, |s| s.len());
+        let witness_len = input
+            .final_script_witness
+            .as_ref()
+            .map_or(0, |w| w.size() - 1);
+        return Some(
+            Weight::from_non_witness_data_size(script_sig_len as u64)
+                + Weight::from_witness_data_size(witness_len as u64),
+        );
+    }
+    let descriptor = if let Some(ws) = &input.witness_script {
+        let ms = Miniscript::parse_insane(ws).ok()?;
+        if script_pubkey.is_p2sh() {
+            Descriptor::<bitcoin::PublicKey>::new_sh_wsh(ms)
+        } else {
+            Descriptor::new_wsh(ms)
+        }
+    } else if let Some(rs) = &input.redeem_script {
+        Descriptor::new_sh(Miniscript::parse_insane(rs).ok()?)
+    } else if script_pubkey.is_p2wpkh() {
+        let key = input.bip32_derivation.keys().next()?;
+        Descriptor::new_wpkh(bitcoin::PublicKey::new(*key))
+    } else if script_pubkey.is_p2tr() && input.tap_merkle_root.is_none() {
+        let internal_key = input.tap_internal_key?;
+        let key = bitcoin::PublicKey::new(internal_key.public_key(Parity::Even));
+        Descriptor::new_tr(key, None)
+    } else {
+        return None;
+    }
+    .ok()?;
+    descriptor.max_weight_to_satisfy().ok()
+}
+
+pub(crate) async fn read_psbt(path: &Path) -> Result<Psbt, APIError> {
+    let bytes = tokio::fs::read(path).await?;
+    let psbt = parse_psbt(&bytes).map_err(|e| APIError::InvalidPsbt(e.to_string()))?;
+    if psbt.unsigned_tx.input.is_empty() {
+        return Err(APIError::InvalidPsbt(s!("transaction has no inputs")));
+    }
+    if psbt.unsigned_tx.output.is_empty() {
+        return Err(APIError::InvalidPsbt(s!("transaction has no outputs")));
+    }
+    if output_amount(&psbt).is_none_or(|amount| amount > Amount::MAX_MONEY) {
+        return Err(APIError::InvalidPsbt(s!(
+            "transaction outputs exceed the maximum amount of bitcoin"
+        )));
+    }
+    Ok(psbt)
+}
+
+// PSBTs are accepted both in binary and base64 encoding
+fn parse_psbt(bytes: &[u8]) -> Result<Psbt, PsbtError> {
+    Psbt::deserialize(bytes).or_else(|e| {
+        std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .ok_or(e)
+    })
+}
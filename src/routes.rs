@@ -1,33 +1,87 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use amplify::s;
 use axum::{
     Json,
-    body::Body,
+    body::{Body, Bytes},
     extract::{Multipart, State},
     http::{HeaderMap, HeaderValue, StatusCode, header},
-    response::{IntoResponse, Response},
+    response::{
+        IntoResponse, Response,
+        sse::{Event as SseEvent, KeepAlive, Sse},
+    },
 };
 use axum_extra::extract::WithRejection;
-use sea_orm::{ActiveValue, DeriveActiveEnum, EnumIter};
+use biscuit_auth::Biscuit;
+use bitcoin::{Network, NetworkKind, Psbt, Txid};
+use futures_util::{Stream, StreamExt, TryStreamExt, stream, stream::BoxStream};
+use sea_orm::{ActiveValue, DatabaseTransaction, DeriveActiveEnum, EnumIter};
 use serde::{Deserialize, Serialize};
-use tokio::{fs::File, io::AsyncWriteExt};
+use tokio::{fs::File, io::AsyncWriteExt, time::Instant};
 use tokio_util::io::ReaderStream;
 
 use crate::{
-    auth::{AuthenticatedCosigner, AuthenticatedUser},
-    database::entities::{cosigner_op_status, next_address_index, op_file, operation},
+    auth::{AuthenticatedCosigner, AuthenticatedUser, get_token_role_and_xpub},
+    database::{
+        OperationFilter,
+        entities::{
+            allowlist_address, allowlist_update, cosigner_op_status, cosigner_response, event,
+            next_address_index, op_file, operation, outflow, revoked_token, webhook_delivery,
+        },
+    },
     error::APIError,
+    policy::{apply_policies, apply_spending_limits, find_non_allowlisted_output},
+    psbt::{check_psbt_network, check_psbt_signatures, combine_psbts, read_psbt, summarize_psbt},
     startup::{AppState, MAX_RGB_LIB_VERSION, MIN_RGB_LIB_VERSION},
-    utils::{compute_file_id, get_threshold_for_operation, no_cancel, now, persist_temp_file},
+    utils::{
+        compute_file_id, get_threshold_for_operation, hex_str_to_vec, no_cancel, now,
+        parse_address, persist_temp_file, tar_end, tar_entry_header, tar_entry_len,
+        tar_entry_padding,
+    },
 };
 
-pub(crate) const AUTO_APPROVED_OPS: [OperationType; 3] = [
+pub(crate) const DEFAULT_AUTO_APPROVED_OPS: [OperationType; 3] = [
     OperationType::Issuance,
     OperationType::BlindReceive,
     OperationType::WitnessReceive,
 ];
 
+pub(crate) const WEBHOOK_EVENTS: [EventType; 6] = [
+    EventType::OperationPosted,
+    EventType::OperationApproved,
+    EventType::OperationDiscarded,
+    EventType::OperationApprovalDelayed,
+    EventType::OperationVetoed,
+    EventType::OperationDiverged,
+];
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+pub(crate) const MAX_LIST_OPERATIONS_LIMIT: u8 = 100;
+
+pub(crate) const MAX_LIST_WEBHOOK_DELIVERIES_LIMIT: u8 = 100;
+
+pub(crate) const MAX_WAIT_SECS: u32 = 60;
+
+pub(crate) const MAX_SYNC_OPERATIONS: u16 = 500;
+
+pub(crate) const SYNC_ARCHIVE_OPERATIONS_PATH: &str = "operations.json";
+pub(crate) const SYNC_ARCHIVE_FILES_DIR: &str = "files";
+
+fn get_processing_result(status: &cosigner_op_status::Model) -> Option<ProcessingResult> {
+    Some(ProcessingResult {
+        success: status.result_success?,
+        txid: status.result_txid.clone(),
+        state_hash: status.result_state_hash.clone()?,
+    })
+}
+
 impl AppState {
     pub(crate) async fn get_operation_by_idx_with_files(
         &self,
@@ -35,103 +89,724 @@ impl AppState {
         cosigner_idx: Option<i32>,
     ) -> Result<Option<OperationResponse>, APIError> {
         // get operation from DB
-        let Some(op) = self.database.get_operation_by_idx(operation_idx).await? else {
+        let Some(op) = self
+            .database
+            .get_operation_by_idx(operation_idx, None)
+            .await?
+        else {
             return Ok(None);
         };
 
-        // get initiator cosigner from DB
-        let initiator = self
-            .database
-            .get_cosigner_by_idx(op.initiator_idx)
+        Ok(self
+            .get_operation_responses(vec![op], cosigner_idx, None)
             .await?
-            .expect("initiator should be set");
+            .pop())
+    }
 
-        // get cosigner op status entries with cosigners from DB
-        let status_entries_with_cosigner = self
+    /// Build the responses of the given operations, getting their related entries from the DB
+    /// with one query per table
+    pub(crate) async fn get_operation_responses(
+        &self,
+        ops: Vec<operation::Model>,
+        cosigner_idx: Option<i32>,
+        txn: Option<&DatabaseTransaction>,
+    ) -> Result<Vec<OperationResponse>, APIError> {
+        // get the entries related to the operations from DB, grouped by operation
+        let operation_idxs: Vec<_> = ops.iter().map(|op| op.idx).collect();
+        let mut status_entries_by_op: HashMap<i32, Vec<cosigner_op_status::Model>> = HashMap::new();
+        for status in self
             .database
-            .get_cosigner_op_status_with_cosigners_by_operation_idx(op.idx)
-            .await?;
+            .iter_cosigner_op_status_by_operation_idxs(&operation_idxs, txn)
+            .await?
+        {
+            status_entries_by_op
+                .entry(status.operation_idx)
+                .or_default()
+                .push(status);
+        }
+        let mut op_files_by_op: HashMap<i32, Vec<op_file::Model>> = HashMap::new();
+        for file in self
+            .database
+            .iter_op_files_by_operation_idxs(&operation_idxs, txn)
+            .await?
+        {
+            op_files_by_op
+                .entry(file.operation_idx)
+                .or_default()
+                .push(file);
+        }
+        let mut allowlist_updates_by_op: HashMap<i32, AllowlistUpdate> = HashMap::new();
+        for update in self
+            .database
+            .iter_allowlist_updates_by_operation_idxs(&operation_idxs, txn)
+            .await?
+        {
+            let allowlist_update = allowlist_updates_by_op
+                .entry(update.operation_idx)
+                .or_default();
+            if update.add {
+                allowlist_update.add.push(update.address);
+            } else {
+                allowlist_update.remove.push(update.address);
+            }
+        }
+        let mut response_history_by_op: HashMap<i32, Vec<CosignerResponseEntry>> = HashMap::new();
+        for response in self
+            .database
+            .iter_cosigner_responses_by_operation_idxs(&operation_idxs, txn)
+            .await?
+        {
+            response_history_by_op
+                .entry(response.operation_idx)
+                .or_default()
+                .push(CosignerResponseEntry {
+                    cosigner_xpub: self.cosigners_by_idx[&response.cosigner_idx].clone(),
+                    ack: response.ack,
+                    responded_at: response.responded_at,
+                });
+        }
 
-        // extract my response and processed_at if cosigner_idx is provided
-        let mut my_response = None;
-        let mut processed_at = None;
-        if let Some(cosigner_idx) = cosigner_idx {
-            for (status, _) in &status_entries_with_cosigner {
-                if status.cosigner_idx == cosigner_idx {
-                    my_response = status.ack;
-                    processed_at = status.processed_at;
-                    break;
+        // get the xPubs of the cosigners holding the veto role
+        let veto_holders: HashSet<_> = self
+            .veto_holders
+            .iter()
+            .map(|idx| self.cosigners_by_idx[idx].clone())
+            .collect();
+
+        let mut responses = Vec::with_capacity(ops.len());
+        for op in ops {
+            let initiator_xpub = self.cosigners_by_idx[&op.initiator_idx].clone();
+            let mut status_entries = status_entries_by_op.remove(&op.idx).unwrap_or_default();
+            status_entries.sort_by_key(|s| s.cosigner_idx);
+            let op_files = op_files_by_op.remove(&op.idx).unwrap_or_default();
+
+            // extract my response and processed_at if cosigner_idx is provided
+            let my_status =
+                cosigner_idx.and_then(|idx| status_entries.iter().find(|s| s.cosigner_idx == idx));
+            let my_response = my_status.and_then(|s| s.ack);
+            let processed_at = my_status.and_then(|s| s.processed_at);
+
+            // list the operation files, PSBTs along with the cosigners' responses
+            let mut files = Vec::new();
+            for file in op_files.iter().filter(|f| f.r#type != FileType::Psbt) {
+                // the combined PSBT is produced by the bridge, not posted by a cosigner
+                let posted_by_xpub = if file.r#type == FileType::CombinedPsbt {
+                    None
+                } else {
+                    Some(initiator_xpub.clone())
+                };
+                files.push(FileMetadata {
+                    file_id: file.file_id.clone(),
+                    r#type: file.r#type,
+                    posted_by_xpub,
+                    size_bytes: self.get_op_file_size(file).await?,
+                });
+            }
+
+            // list the cosigners' current PSBT files, the ones superseded by a revised response
+            // are left out
+            for status in &status_entries {
+                if let Some(psbt_op_file_idx) = status.psbt_op_file_idx {
+                    let psbt_file = op_files
+                        .iter()
+                        .find(|f| f.idx == psbt_op_file_idx)
+                        .expect("PSBT op file should exist");
+                    files.push(FileMetadata {
+                        file_id: psbt_file.file_id.clone(),
+                        r#type: FileType::Psbt,
+                        posted_by_xpub: Some(self.cosigners_by_idx[&status.cosigner_idx].clone()),
+                        size_bytes: self.get_op_file_size(psbt_file).await?,
+                    });
                 }
             }
-        }
 
-        // get operation files from DB and read their metadata from filesystem
-        let op_files = self.database.get_op_files_by_operation_idx(op.idx).await?;
-        let mut files = Vec::new();
-        for file in op_files {
-            let file_path = self.files_dir.join(&file.file_id);
-            let metadata = tokio::fs::metadata(&file_path).await?;
-            files.push(FileMetadata {
-                file_id: file.file_id,
-                r#type: file.r#type,
-                posted_by_xpub: initiator.xpub.clone(),
-                size_bytes: metadata.len(),
+            // calculate acked_by and nacked_by sets
+            let mut acked_by = HashSet::new();
+            let mut nacked_by = HashSet::new();
+            for status in &status_entries {
+                let xpub = self.cosigners_by_idx[&status.cosigner_idx].clone();
+                match status.ack {
+                    Some(true) => acked_by.insert(xpub),
+                    Some(false) => nacked_by.insert(xpub),
+                    None => continue,
+                };
+            }
+
+            // get the summary of the initiator's PSBT, if any
+            let psbt_summary = match &op.psbt_summary {
+                Some(psbt_summary) => Some(serde_json::from_str(psbt_summary).map_err(|e| {
+                    APIError::Unexpected(format!("failed to parse PSBT summary: {e}"))
+                })?),
+                // operations posted before summaries were stored have theirs computed from the
+                // PSBT
+                None => {
+                    let initiator_psbt_op_file_idx = status_entries
+                        .iter()
+                        .find(|s| s.cosigner_idx == op.initiator_idx)
+                        .and_then(|s| s.psbt_op_file_idx);
+                    match initiator_psbt_op_file_idx {
+                        Some(idx) => match self.get_op_file_psbt(idx).await {
+                            Ok(psbt) => Some(self.summarize_psbt(&psbt)),
+                            // PSBTs stored before validation was introduced may be invalid
+                            Err(APIError::InvalidPsbt(_)) => None,
+                            Err(e) => return Err(e),
+                        },
+                        None => None,
+                    }
+                }
+            };
+
+            // get the allowlist update proposed by the operation, if any
+            let allowlist_update = if op.r#type == OperationType::UpdateAllowlist {
+                Some(allowlist_updates_by_op.remove(&op.idx).unwrap_or_default())
+            } else {
+                None
+            };
+
+            // get the history of the responses, oldest first
+            let response_history = response_history_by_op.remove(&op.idx).unwrap_or_default();
+
+            // weigh the responses
+            let (acked_weight, nacked_weight) = self.get_response_weights(&status_entries);
+
+            // get threshold for operation
+            let threshold = self.get_operation_threshold(&op);
+
+            // calculate expiry time for pending operations, if a timeout is configured
+            let expires_at = self.get_operation_expiry(&op);
+
+            // get the processing results reported by the cosigners, ordered by cosigner
+            let processing_reports = status_entries
+                .iter()
+                .filter_map(|status| {
+                    Some(ProcessingReport {
+                        cosigner_xpub: self.cosigners_by_idx[&status.cosigner_idx].clone(),
+                        processed_at: status.processed_at?,
+                        result: get_processing_result(status)?,
+                    })
+                })
+                .collect();
+
+            responses.push(OperationResponse {
+                operation_idx: op.idx,
+                initiator_xpub,
+                created_at: op.created_at,
+                operation_type: op.r#type,
+                status: op.status,
+                acked_by,
+                nacked_by,
+                acked_weight,
+                nacked_weight,
+                total_weight: self.get_total_weight(),
+                veto_holders: veto_holders.clone(),
+                response_history,
+                threshold,
+                my_response,
+                processed_at,
+                expires_at,
+                delay_ends_at: op.delay_ends_at,
+                files,
+                psbt_summary,
+                allowlist_update,
+                processing_reports,
+                divergent: op.divergent,
+                chain_status: op.chain_status,
+                txid: op.txid,
+                block_height: op.block_height,
             });
         }
+        Ok(responses)
+    }
+
+    // files stored before their size was recorded have it read from the filesystem
+    async fn get_op_file_size(&self, file: &op_file::Model) -> Result<u64, APIError> {
+        Ok(match file.size_bytes {
+            Some(size_bytes) => size_bytes as u64,
+            None => tokio::fs::metadata(self.files_dir.join(&file.file_id))
+                .await?
+                .len(),
+        })
+    }
+
+    pub(crate) async fn get_address_allowlist(&self) -> Result<HashSet<String>, APIError> {
+        Ok(self
+            .database
+            .iter_allowlist_addresses()
+            .await?
+            .into_iter()
+            .map(|a| a.address)
+            .collect())
+    }
+
+    /// Move an operation to the approved state, combining the ACK PSBTs or applying the allowlist
+    /// update. The PSBT of a responding cosigner not yet committed to the DB can be provided.
+    pub(crate) async fn approve_operation(
+        &self,
+        op: operation::Model,
+        responder_psbt: Option<(i32, Psbt)>,
+        txn: &DatabaseTransaction,
+    ) -> Result<event::Model, APIError> {
+        if op.r#type == OperationType::UpdateAllowlist {
+            // apply the allowlist update
+            for update in self
+                .database
+                .iter_allowlist_updates_by_operation_idx(op.idx, Some(txn))
+                .await?
+            {
+                if update.add {
+                    let address = allowlist_address::ActiveModel {
+                        address: ActiveValue::Set(update.address),
+                        operation_idx: ActiveValue::Set(op.idx),
+                        ..Default::default()
+                    };
+                    self.database.set_allowlist_address(address, txn).await?;
+                } else {
+                    self.database
+                        .delete_allowlist_address(&update.address, txn)
+                        .await?;
+                }
+            }
+        } else {
+            // combine the ACK PSBTs and save the result
+            let responder_idx = responder_psbt.as_ref().map(|(idx, _)| *idx);
+            let mut psbts = Vec::new();
+            for status_entry in self
+                .database
+                .iter_cosigner_op_status_by_operation_idx(op.idx, txn)
+                .await?
+            {
+                if status_entry.ack != Some(true)
+                    || Some(status_entry.cosigner_idx) == responder_idx
+                {
+                    continue;
+                }
+                if let Some(psbt_op_file_idx) = status_entry.psbt_op_file_idx {
+                    psbts.push(self.get_op_file_psbt(psbt_op_file_idx).await?);
+                }
+            }
+            psbts.extend(responder_psbt.map(|(_, psbt)| psbt));
+            // operations without PSBTs (e.g. only carrying a consignment) have nothing to combine
+            if let Some(combined_psbt) = combine_psbts(psbts)? {
+                self.record_outflow(op.idx, &self.summarize_psbt(&combined_psbt), txn)
+                    .await?;
+                let temp_file = tempfile::Builder::new()
+                    .prefix("tmp_")
+                    .tempfile_in(&self.files_dir)?;
+                let mut async_file = File::from_std(temp_file.reopen()?);
+                async_file.write_all(&combined_psbt.serialize()).await?;
+                async_file.flush().await?;
+                let file_id = compute_file_id(temp_file.path()).await?;
+                let file_path = self.files_dir.join(&file_id);
+                if !file_path.exists() {
+                    persist_temp_file(temp_file, &file_path).await?;
+                }
+                let size_bytes = tokio::fs::metadata(&file_path).await?.len();
+                let db_file = op_file::ActiveModel {
+                    file_id: ActiveValue::Set(file_id),
+                    r#type: ActiveValue::Set(FileType::CombinedPsbt),
+                    operation_idx: ActiveValue::Set(op.idx),
+                    size_bytes: ActiveValue::Set(Some(size_bytes as i64)),
+                    ..Default::default()
+                };
+                self.database.set_op_file(db_file, txn).await?;
+            }
+        }
 
-        // get other cosigner's PSBT files from DB and read their metadata from filesystem
-        for (status, cosigner) in &status_entries_with_cosigner {
-            if cosigner.xpub == initiator.xpub {
-                continue;
+        // update operation status
+        let operation_idx = op.idx;
+        let mut operation: operation::ActiveModel = op.into();
+        operation.status = ActiveValue::Set(OperationStatus::Approved);
+        self.database.update_operation(operation, txn).await?;
+        self.record_event(EventType::OperationApproved, operation_idx, None, txn)
+            .await
+    }
+
+    /// Weigh the responses to a pending operation, moving it to the approved (possibly pending
+    /// delay) or discarded state if they settle it. The PSBT of a responding cosigner not yet
+    /// committed to the DB can be provided.
+    pub(crate) async fn update_operation_status(
+        &self,
+        op: operation::Model,
+        responder_psbt: Option<(i32, Psbt)>,
+        txn: &DatabaseTransaction,
+    ) -> Result<Option<event::Model>, APIError> {
+        // weigh ACKs and NACKs to determine new status
+        let status_entries = self
+            .database
+            .iter_cosigner_op_status_by_operation_idx(op.idx, txn)
+            .await?;
+        let (acked_weight, nacked_weight) = self.get_response_weights(&status_entries);
+        let threshold = self
+            .get_operation_threshold(&op)
+            .expect("threshold should be set for non-auto-approved operations")
+            as u32;
+        let vetoed = status_entries
+            .iter()
+            .any(|s| s.ack == Some(false) && self.veto_holders.contains(&s.cosigner_idx));
+        let total_weight = self.get_total_weight();
+        let new_status = if vetoed {
+            // a NACK from a veto holder discards the operation regardless of the ACKs
+            OperationStatus::Discarded
+        } else if acked_weight >= threshold {
+            if self.approval_delays.contains_key(&op.r#type) {
+                OperationStatus::ApprovedPendingDelay
+            } else {
+                OperationStatus::Approved
             }
-            if let Some(psbt_op_file_idx) = &status.psbt_op_file_idx {
-                let psbt_file = self
-                    .database
-                    .get_op_file_by_idx(*psbt_op_file_idx)
+        } else if nacked_weight > (total_weight - threshold) {
+            OperationStatus::Discarded
+        } else {
+            return Ok(None);
+        };
+
+        // update operation status
+        tracing::debug!("Operation new status: {:?}", new_status);
+        let operation_idx = op.idx;
+        let event = match new_status {
+            OperationStatus::Approved => self.approve_operation(op, responder_psbt, txn).await?,
+            OperationStatus::ApprovedPendingDelay => {
+                let delay = self.approval_delays[&op.r#type];
+                let mut operation: operation::ActiveModel = op.into();
+                operation.status = ActiveValue::Set(new_status);
+                operation.delay_ends_at =
+                    ActiveValue::Set(Some(now().unix_timestamp() + delay as i64));
+                self.database.update_operation(operation, txn).await?;
+                self.record_event(
+                    EventType::OperationApprovalDelayed,
+                    operation_idx,
+                    None,
+                    txn,
+                )
+                .await?
+            }
+            _ => {
+                let mut operation: operation::ActiveModel = op.into();
+                operation.status = ActiveValue::Set(new_status);
+                self.database.update_operation(operation, txn).await?;
+                self.record_event(EventType::OperationDiscarded, operation_idx, None, txn)
                     .await?
-                    .expect("PSBT op file should exist");
-                let file_path = self.files_dir.join(&psbt_file.file_id);
-                let metadata = tokio::fs::metadata(&file_path).await?;
-                files.push(FileMetadata {
-                    file_id: psbt_file.file_id,
-                    r#type: FileType::Psbt,
-                    posted_by_xpub: cosigner.xpub.clone(),
-                    size_bytes: metadata.len(),
-                });
             }
+        };
+        Ok(Some(event))
+    }
+
+    pub(crate) async fn update_chain_status(
+        &self,
+        op: operation::Model,
+        chain_status: ChainStatus,
+        txid: Option<String>,
+        block_height: Option<u32>,
+        cosigner_idx: Option<i32>,
+        txn: &DatabaseTransaction,
+    ) -> Result<event::Model, APIError> {
+        let operation_idx = op.idx;
+        let mut operation: operation::ActiveModel = op.into();
+        operation.chain_status = ActiveValue::Set(Some(chain_status));
+        operation.txid = ActiveValue::Set(txid);
+        operation.block_height = ActiveValue::Set(block_height);
+        self.database.update_operation(operation, txn).await?;
+        let event_type = match chain_status {
+            ChainStatus::Broadcast => EventType::OperationBroadcast,
+            ChainStatus::Confirmed => EventType::OperationConfirmed,
+            ChainStatus::Failed => EventType::OperationFailed,
+        };
+        self.record_event(event_type, operation_idx, cosigner_idx, txn)
+            .await
+    }
+
+    /// Get the time a pending operation expires at, if a pending timeout is configured
+    pub(crate) fn get_operation_expiry(&self, op: &operation::Model) -> Option<i64> {
+        if op.status != OperationStatus::Pending {
+            return None;
+        }
+        let timeout = i64::try_from(self.pending_timeout?).ok()?;
+        op.created_at.checked_add(timeout)
+    }
+
+    /// Whether a pending operation is past its deadline, even if not marked as expired yet
+    pub(crate) fn is_operation_expired(&self, op: &operation::Model) -> bool {
+        self.get_operation_expiry(op)
+            .is_some_and(|expires_at| now().unix_timestamp() >= expires_at)
+    }
+
+    pub(crate) fn get_operation_threshold(&self, op: &operation::Model) -> Option<u8> {
+        // operations posted before thresholds were stored use the one of their type
+        op.threshold
+            .or_else(|| self.get_operation_type_threshold(&op.r#type))
+    }
+
+    /// Get the threshold of an operation type, falling back to the colored or vanilla one
+    pub(crate) fn get_operation_type_threshold(&self, op_type: &OperationType) -> Option<u8> {
+        self.thresholds.get(op_type).copied().or_else(|| {
+            get_threshold_for_operation(
+                op_type,
+                self.threshold_vanilla,
+                self.threshold_colored,
+                &self.auto_approved_ops,
+            )
+        })
+    }
+
+    /// Sum the weights of the cosigners who ACKed and NACKed an operation
+    pub(crate) fn get_response_weights<'a>(
+        &self,
+        status_entries: impl IntoIterator<Item = &'a cosigner_op_status::Model>,
+    ) -> (u32, u32) {
+        status_entries.into_iter().fold((0, 0), |(ack, nack), s| {
+            let weight = self.cosigner_weights_by_idx[&s.cosigner_idx] as u32;
+            match s.ack {
+                Some(true) => (ack + weight, nack),
+                Some(false) => (ack, nack + weight),
+                None => (ack, nack),
+            }
+        })
+    }
+
+    pub(crate) fn get_total_weight(&self) -> u32 {
+        self.cosigner_weights_by_idx
+            .values()
+            .map(|w| *w as u32)
+            .sum()
+    }
+
+    pub(crate) async fn get_spending_limits_usage(
+        &self,
+    ) -> Result<Vec<SpendingLimitUsage>, APIError> {
+        let Some(longest_window) = self
+            .spending_limits
+            .iter()
+            .map(|l| l.window.seconds())
+            .max()
+        else {
+            return Ok(vec![]);
+        };
+        let now = now().unix_timestamp();
+        let outflows = self
+            .database
+            .iter_outflows_approved_after(now - longest_window)
+            .await?;
+        Ok(self
+            .spending_limits
+            .iter()
+            .map(|limit| {
+                let used_sat = outflows
+                    .iter()
+                    .filter(|o| o.approved_at > now - limit.window.seconds())
+                    .map(|o| o.amount_sat as u64)
+                    .sum();
+                SpendingLimitUsage {
+                    window: limit.window,
+                    limit_sat: limit.amount_sat,
+                    used_sat,
+                    remaining_sat: limit.amount_sat.saturating_sub(used_sat),
+                }
+            })
+            .collect())
+    }
+
+    pub(crate) async fn record_outflow(
+        &self,
+        operation_idx: i32,
+        psbt_summary: &PsbtSummary,
+        txn: &DatabaseTransaction,
+    ) -> Result<(), APIError> {
+        // when the fee is unknown, at least the sent amount is leaving the multisig
+        let amount_sat = psbt_summary
+            .outflow()
+            .unwrap_or_else(|| psbt_summary.sent_amount());
+        if amount_sat == 0 {
+            return Ok(());
         }
+        let amount_sat = i64::try_from(amount_sat)
+            .map_err(|_| APIError::Unexpected(format!("outflow of {amount_sat} sats too large")))?;
+        let db_outflow = outflow::ActiveModel {
+            operation_idx: ActiveValue::Set(operation_idx),
+            amount_sat: ActiveValue::Set(amount_sat),
+            approved_at: ActiveValue::Set(now().unix_timestamp()),
+            ..Default::default()
+        };
+        self.database.set_outflow(db_outflow, txn).await?;
+        Ok(())
+    }
+
+    pub(crate) fn summarize_psbt(&self, psbt: &Psbt) -> PsbtSummary {
+        let xpubs: Vec<_> = self.cosigner_keys_by_idx.values().cloned().collect();
+        summarize_psbt(psbt, &xpubs, self.network)
+    }
+
+    pub(crate) async fn get_cosigner_psbt(
+        &self,
+        operation_idx: i32,
+        cosigner_idx: i32,
+    ) -> Result<Option<Psbt>, APIError> {
+        let Some(psbt_op_file_idx) = self
+            .database
+            .get_cosigner_op_status_entry(cosigner_idx, operation_idx)
+            .await?
+            .and_then(|s| s.psbt_op_file_idx)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(self.get_op_file_psbt(psbt_op_file_idx).await?))
+    }
+
+    /// Get the first PSBT uploaded for an operation, which fixes its unsigned transaction
+    pub(crate) async fn get_first_psbt(
+        &self,
+        operation_idx: i32,
+    ) -> Result<Option<Psbt>, APIError> {
+        let Some(psbt_file) = self
+            .database
+            .get_first_op_file_by_type(operation_idx, FileType::Psbt)
+            .await?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(
+            read_psbt(&self.files_dir.join(&psbt_file.file_id)).await?,
+        ))
+    }
+
+    pub(crate) async fn get_op_file_psbt(&self, op_file_idx: i32) -> Result<Psbt, APIError> {
+        let psbt_file = self
+            .database
+            .get_op_file_by_idx(op_file_idx)
+            .await?
+            .expect("PSBT op file should exist");
+        read_psbt(&self.files_dir.join(&psbt_file.file_id)).await
+    }
 
-        // calculate acked_by and nacked_by sets
-        let mut acked_by = HashSet::new();
-        let mut nacked_by = HashSet::new();
-        for (status_entry, cosigner) in &status_entries_with_cosigner {
-            match status_entry.ack {
-                Some(true) => acked_by.insert(cosigner.xpub.clone()),
-                Some(false) => nacked_by.insert(cosigner.xpub.clone()),
-                None => continue,
+    pub(crate) async fn record_event(
+        &self,
+        event_type: EventType,
+        operation_idx: i32,
+        cosigner_idx: Option<i32>,
+        txn: &DatabaseTransaction,
+    ) -> Result<event::Model, APIError> {
+        let now = now().unix_timestamp();
+        let db_event = event::ActiveModel {
+            r#type: ActiveValue::Set(event_type),
+            operation_idx: ActiveValue::Set(operation_idx),
+            cosigner_idx: ActiveValue::Set(cosigner_idx),
+            created_at: ActiveValue::Set(now),
+            ..Default::default()
+        };
+        let event = self.database.set_event(db_event, txn).await?;
+
+        // queue webhook deliveries, so they're persisted along with the event, with a payload
+        // reflecting the operation as of the event
+        if WEBHOOK_EVENTS.contains(&event_type) && !self.webhooks.is_empty() {
+            let op = self
+                .database
+                .get_operation_by_idx(operation_idx, Some(txn))
+                .await?
+                .ok_or(APIError::Unexpected(s!("event operation not found")))?;
+            let operation = self
+                .get_operation_responses(vec![op], None, Some(txn))
+                .await?
+                .pop()
+                .expect("a response for each operation");
+            let payload = WebhookPayload {
+                event: self.get_event_response(event.clone()),
+                operation,
             };
+            let payload = serde_json::to_string(&payload).map_err(|e| {
+                APIError::Unexpected(format!("failed to serialize webhook payload: {e}"))
+            })?;
+            for webhook in &self.webhooks {
+                let delivery = webhook_delivery::ActiveModel {
+                    event_idx: ActiveValue::Set(event.idx),
+                    url: ActiveValue::Set(webhook.url.clone()),
+                    payload: ActiveValue::Set(Some(payload.clone())),
+                    status: ActiveValue::Set(WebhookDeliveryStatus::Pending),
+                    attempts: ActiveValue::Set(0),
+                    next_attempt_at: ActiveValue::Set(now),
+                    last_error: ActiveValue::Set(None),
+                    delivered_at: ActiveValue::Set(None),
+                    ..Default::default()
+                };
+                self.database.set_webhook_delivery(delivery, txn).await?;
+            }
+        }
+
+        Ok(event)
+    }
+
+    pub(crate) fn publish_events(&self, events: Vec<event::Model>) {
+        for event in events {
+            // sending only fails if there are no subscribers, in which case there's nothing to do
+            let _ = self.event_sender.send(self.get_event_response(event));
+        }
+        self.operation_notify.notify_waiters();
+        self.webhook_notify.notify_one();
+    }
+
+    pub(crate) fn get_event_response(&self, event: event::Model) -> EventResponse {
+        EventResponse {
+            event_idx: event.idx,
+            event_type: event.r#type,
+            operation_idx: event.operation_idx,
+            cosigner_xpub: event
+                .cosigner_idx
+                .and_then(|idx| self.cosigners_by_idx.get(&idx).cloned()),
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "u8", db_type = "TinyUnsigned")]
+pub(crate) enum BitcoinNetwork {
+    #[default]
+    #[sea_orm(num_value = 1)]
+    Mainnet = 1,
+    #[sea_orm(num_value = 2)]
+    Testnet = 2,
+    #[sea_orm(num_value = 3)]
+    Testnet4 = 3,
+    #[sea_orm(num_value = 4)]
+    Signet = 4,
+    #[sea_orm(num_value = 5)]
+    Regtest = 5,
+}
+
+impl BitcoinNetwork {
+    pub(crate) fn network(&self) -> Network {
+        match self {
+            BitcoinNetwork::Mainnet => Network::Bitcoin,
+            BitcoinNetwork::Testnet => Network::Testnet,
+            BitcoinNetwork::Testnet4 => Network::Testnet4,
+            BitcoinNetwork::Signet => Network::Signet,
+            BitcoinNetwork::Regtest => Network::Regtest,
         }
+    }
 
-        // get threshold for operation type
-        let threshold =
-            get_threshold_for_operation(&op.r#type, self.threshold_vanilla, self.threshold_colored);
+    pub(crate) fn network_kind(&self) -> NetworkKind {
+        match self {
+            BitcoinNetwork::Mainnet => NetworkKind::Main,
+            _ => NetworkKind::Test,
+        }
+    }
 
-        Ok(Some(OperationResponse {
-            operation_idx: op.idx,
-            initiator_xpub: initiator.xpub,
-            created_at: op.created_at,
-            operation_type: op.r#type,
-            status: op.status,
-            acked_by,
-            nacked_by,
-            threshold,
-            my_response,
-            processed_at,
-            files,
-        }))
+    /// BIP44 coin type used in derivation paths
+    pub(crate) fn coin_type(&self) -> u32 {
+        match self.network_kind() {
+            NetworkKind::Main => 0,
+            NetworkKind::Test => 1,
+        }
     }
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct AllowlistUpdate {
+    #[serde(default)]
+    pub(crate) add: Vec<String>,
+    #[serde(default)]
+    pub(crate) remove: Vec<String>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub(crate) struct BumpAddressIndicesRequest {
     pub(crate) count: u8,
@@ -143,14 +818,84 @@ pub(crate) struct BumpAddressIndicesResponse {
     pub(crate) first: u32,
 }
 
+#[derive(Deserialize, Serialize)]
+pub(crate) struct CancelOperationRequest {
+    pub(crate) operation_idx: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "u8", db_type = "TinyUnsigned")]
+pub(crate) enum ChainStatus {
+    #[sea_orm(num_value = 1)]
+    Broadcast = 1,
+    #[sea_orm(num_value = 2)]
+    Confirmed = 2,
+    #[sea_orm(num_value = 3)]
+    Failed = 3,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct CosignerInfo {
+    pub(crate) xpub: String,
+    pub(crate) fingerprint: String,
+    pub(crate) weight: u8,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct CosignerResponseEntry {
+    pub(crate) cosigner_xpub: String,
+    pub(crate) ack: bool,
+    pub(crate) responded_at: i64,
+}
+
 #[derive(Deserialize, Serialize)]
 pub(crate) struct EmptyResponse {}
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct EventResponse {
+    pub(crate) event_idx: i32,
+    pub(crate) event_type: EventType,
+    pub(crate) operation_idx: i32,
+    pub(crate) cosigner_xpub: Option<String>,
+    pub(crate) created_at: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "u8", db_type = "TinyUnsigned")]
+pub(crate) enum EventType {
+    #[sea_orm(num_value = 1)]
+    OperationPosted = 1,
+    #[sea_orm(num_value = 2)]
+    CosignerResponded = 2,
+    #[sea_orm(num_value = 3)]
+    OperationApproved = 3,
+    #[sea_orm(num_value = 4)]
+    OperationDiscarded = 4,
+    #[sea_orm(num_value = 5)]
+    CosignerProcessed = 5,
+    #[sea_orm(num_value = 6)]
+    OperationCancelled = 6,
+    #[sea_orm(num_value = 7)]
+    OperationExpired = 7,
+    #[sea_orm(num_value = 8)]
+    OperationApprovalDelayed = 8,
+    #[sea_orm(num_value = 9)]
+    OperationVetoed = 9,
+    #[sea_orm(num_value = 10)]
+    OperationDiverged = 10,
+    #[sea_orm(num_value = 11)]
+    OperationBroadcast = 11,
+    #[sea_orm(num_value = 12)]
+    OperationConfirmed = 12,
+    #[sea_orm(num_value = 13)]
+    OperationFailed = 13,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct FileMetadata {
     pub(crate) file_id: String,
     pub(crate) r#type: FileType,
-    pub(crate) posted_by_xpub: String,
+    pub(crate) posted_by_xpub: Option<String>,
     pub(crate) size_bytes: u64,
 }
 
@@ -165,6 +910,8 @@ pub(crate) enum FileType {
     OperationData = 3,
     #[sea_orm(num_value = 4)]
     Psbt = 4,
+    #[sea_orm(num_value = 5)]
+    CombinedPsbt = 5,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -186,6 +933,8 @@ pub(crate) struct GetLastProcessedOpIdxResponse {
 #[derive(Deserialize, Serialize)]
 pub(crate) struct GetOperationByIdxRequest {
     pub(crate) operation_idx: i32,
+    pub(crate) wait: Option<u32>,
+    pub(crate) status: Option<OperationStatus>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -194,11 +943,49 @@ pub(crate) struct InfoResponse {
     pub(crate) max_rgb_lib_version: String,
     pub(crate) rgb_lib_version: String,
     pub(crate) last_operation_idx: Option<i32>,
+    pub(crate) network: BitcoinNetwork,
+    pub(crate) cosigners: Vec<CosignerInfo>,
+    pub(crate) auto_approved_ops: Vec<OperationType>,
+    pub(crate) address_allowlist: Vec<String>,
+    pub(crate) spending_limits: Vec<SpendingLimitUsage>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct ListOperationsRequest {
+    pub(crate) limit: u8,
+    pub(crate) cursor: Option<i32>,
+    pub(crate) status: Option<OperationStatus>,
+    pub(crate) operation_type: Option<OperationType>,
+    pub(crate) initiator_xpub: Option<String>,
+    pub(crate) created_from: Option<i64>,
+    pub(crate) created_to: Option<i64>,
+    #[serde(default)]
+    pub(crate) unprocessed_by_me: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct ListOperationsResponse {
+    pub(crate) operations: Vec<OperationResponse>,
+    pub(crate) next_cursor: Option<i32>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct ListWebhookDeliveriesRequest {
+    pub(crate) limit: u8,
+    pub(crate) cursor: Option<i32>,
+    pub(crate) status: Option<WebhookDeliveryStatus>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct ListWebhookDeliveriesResponse {
+    pub(crate) deliveries: Vec<WebhookDeliveryResponse>,
+    pub(crate) next_cursor: Option<i32>,
 }
 
 #[derive(Deserialize, Serialize)]
 pub(crate) struct MarkOperationProcessedRequest {
     pub(crate) operation_idx: i32,
+    pub(crate) result: Option<ProcessingResult>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -210,10 +997,24 @@ pub(crate) struct OperationResponse {
     pub(crate) status: OperationStatus,
     pub(crate) acked_by: HashSet<String>,
     pub(crate) nacked_by: HashSet<String>,
+    pub(crate) acked_weight: u32,
+    pub(crate) nacked_weight: u32,
+    pub(crate) total_weight: u32,
+    pub(crate) veto_holders: HashSet<String>,
+    pub(crate) response_history: Vec<CosignerResponseEntry>,
     pub(crate) threshold: Option<u8>,
     pub(crate) my_response: Option<bool>,
     pub(crate) processed_at: Option<i64>,
+    pub(crate) expires_at: Option<i64>,
+    pub(crate) delay_ends_at: Option<i64>,
     pub(crate) files: Vec<FileMetadata>,
+    pub(crate) psbt_summary: Option<PsbtSummary>,
+    pub(crate) allowlist_update: Option<AllowlistUpdate>,
+    pub(crate) processing_reports: Vec<ProcessingReport>,
+    pub(crate) divergent: bool,
+    pub(crate) chain_status: Option<ChainStatus>,
+    pub(crate) txid: Option<String>,
+    pub(crate) block_height: Option<u32>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
@@ -225,9 +1026,19 @@ pub(crate) enum OperationStatus {
     Approved = 2,
     #[sea_orm(num_value = 3)]
     Discarded = 3,
+    #[sea_orm(num_value = 4)]
+    Cancelled = 4,
+    #[sea_orm(num_value = 5)]
+    Expired = 5,
+    #[sea_orm(num_value = 6)]
+    ApprovedPendingDelay = 6,
+    #[sea_orm(num_value = 7)]
+    Vetoed = 7,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Deserialize, Serialize,
+)]
 #[sea_orm(rs_type = "u8", db_type = "TinyUnsigned")]
 pub(crate) enum OperationType {
     #[sea_orm(num_value = 1)]
@@ -244,6 +1055,8 @@ pub(crate) enum OperationType {
     BlindReceive = 6,
     #[sea_orm(num_value = 7)]
     WitnessReceive = 7,
+    #[sea_orm(num_value = 8)]
+    UpdateAllowlist = 8,
 }
 
 impl TryFrom<u8> for OperationType {
@@ -258,6 +1071,7 @@ impl TryFrom<u8> for OperationType {
             5 => Ok(OperationType::Inflation),
             6 => Ok(OperationType::BlindReceive),
             7 => Ok(OperationType::WitnessReceive),
+            8 => Ok(OperationType::UpdateAllowlist),
             _ => Err(APIError::InvalidOperationType(value)),
         }
     }
@@ -268,12 +1082,133 @@ pub(crate) struct PostOperationResponse {
     pub(crate) operation_idx: i32,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct ProcessingReport {
+    pub(crate) cosigner_xpub: String,
+    pub(crate) processed_at: i64,
+    pub(crate) result: ProcessingResult,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub(crate) struct ProcessingResult {
+    pub(crate) success: bool,
+    pub(crate) txid: Option<String>,
+    pub(crate) state_hash: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct PsbtInputSummary {
+    pub(crate) outpoint: String,
+    pub(crate) amount_sat: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct PsbtOutputSummary {
+    pub(crate) address: Option<String>,
+    pub(crate) script_pubkey: String,
+    pub(crate) amount_sat: u64,
+    pub(crate) is_change: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct PsbtSummary {
+    pub(crate) inputs: Vec<PsbtInputSummary>,
+    pub(crate) outputs: Vec<PsbtOutputSummary>,
+    pub(crate) fee_sat: Option<u64>,
+    pub(crate) fee_rate_sat_per_vb: Option<f64>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub(crate) struct RespondToOperationRequest {
     pub(crate) operation_idx: i32,
     pub(crate) ack: bool,
 }
 
+#[derive(Deserialize, Serialize)]
+pub(crate) struct RevokeTokenRequest {
+    pub(crate) token: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct SpendingLimitUsage {
+    pub(crate) window: SpendingLimitWindow,
+    pub(crate) limit_sat: u64,
+    pub(crate) used_sat: u64,
+    pub(crate) remaining_sat: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum SpendingLimitWindow {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl SpendingLimitWindow {
+    /// Length of the rolling window, in seconds
+    pub(crate) fn seconds(&self) -> i64 {
+        match self {
+            SpendingLimitWindow::Daily => 24 * 60 * 60,
+            SpendingLimitWindow::Weekly => 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct SyncOperationsRequest {
+    pub(crate) after_idx: i32,
+    #[serde(default)]
+    pub(crate) archive: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct SyncOperationsResponse {
+    pub(crate) operations: Vec<OperationResponse>,
+    pub(crate) has_more: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct UpdateChainStatusRequest {
+    pub(crate) operation_idx: i32,
+    pub(crate) chain_status: ChainStatus,
+    pub(crate) txid: Option<String>,
+    pub(crate) block_height: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct VetoOperationRequest {
+    pub(crate) operation_idx: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct WebhookDeliveryResponse {
+    pub(crate) delivery_idx: i32,
+    pub(crate) event_idx: i32,
+    pub(crate) url: String,
+    pub(crate) status: WebhookDeliveryStatus,
+    pub(crate) attempts: u32,
+    pub(crate) next_attempt_at: i64,
+    pub(crate) last_error: Option<String>,
+    pub(crate) delivered_at: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize)]
+#[sea_orm(rs_type = "u8", db_type = "TinyUnsigned")]
+pub(crate) enum WebhookDeliveryStatus {
+    #[sea_orm(num_value = 1)]
+    Pending = 1,
+    #[sea_orm(num_value = 2)]
+    Delivered = 2,
+    #[sea_orm(num_value = 3)]
+    Failed = 3,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct WebhookPayload {
+    pub(crate) event: EventResponse,
+    pub(crate) operation: OperationResponse,
+}
+
 pub(crate) async fn bump_address_indices(
     State(state): State<Arc<AppState>>,
     WithRejection(Json(req), _): WithRejection<Json<BumpAddressIndicesRequest>, APIError>,
@@ -312,6 +1247,147 @@ pub(crate) async fn bump_address_indices(
     Ok(Json(BumpAddressIndicesResponse { first }))
 }
 
+pub(crate) async fn cancel_operation(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedCosigner {
+        idx: cosigner_idx, ..
+    }: AuthenticatedCosigner,
+    WithRejection(Json(req), _): WithRejection<Json<CancelOperationRequest>, APIError>,
+) -> Result<Json<OperationResponse>, APIError> {
+    no_cancel(async move {
+        // acquire write lock to prevent concurrent write operations
+        let _lock = state.write_lock.lock().await;
+
+        // check if request is allowed
+        let op = state
+            .database
+            .get_operation_by_idx(req.operation_idx, None)
+            .await?
+            .ok_or(APIError::OperationNotFound)?;
+        if op.initiator_idx != cosigner_idx {
+            return Err(APIError::CannotCancelOperation(s!(
+                "only the initiator can cancel an operation"
+            )));
+        }
+        if op.status != OperationStatus::Pending {
+            return Err(APIError::CannotCancelOperation(s!(
+                "operation is not pending"
+            )));
+        }
+        if state.is_operation_expired(&op) {
+            return Err(APIError::CannotCancelOperation(s!("operation has expired")));
+        }
+
+        // request is valid, start transaction
+        let txn = state.database.begin_transaction().await?;
+
+        // check no other cosigner has ACKed the operation
+        let status_entries = state
+            .database
+            .iter_cosigner_op_status_by_operation_idx(op.idx, &txn)
+            .await?;
+        if status_entries
+            .iter()
+            .any(|s| s.cosigner_idx != cosigner_idx && s.ack == Some(true))
+        {
+            return Err(APIError::CannotCancelOperation(s!(
+                "operation has already been ACKed by another cosigner"
+            )));
+        }
+
+        // update operation status
+        let mut operation: operation::ActiveModel = op.into();
+        operation.status = ActiveValue::Set(OperationStatus::Cancelled);
+        state.database.update_operation(operation, &txn).await?;
+        tracing::debug!("Operation {} cancelled", req.operation_idx);
+        let event = state
+            .record_event(
+                EventType::OperationCancelled,
+                req.operation_idx,
+                Some(cosigner_idx),
+                &txn,
+            )
+            .await?;
+
+        // commit transaction and notify subscribers
+        txn.commit().await?;
+        state.publish_events(vec![event]);
+
+        // get updated operation response
+        let operation_response = state
+            .get_operation_by_idx_with_files(req.operation_idx, Some(cosigner_idx))
+            .await?
+            .expect("operation should exist after cancellation");
+
+        Ok(Json(operation_response))
+    })
+    .await
+}
+
+pub(crate) async fn events(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<SseEvent, Infallible>>>, APIError> {
+    // get the index of the last event received by the client, if resuming
+    let last_event_idx = match headers.get(LAST_EVENT_ID_HEADER) {
+        Some(value) => value
+            .to_str()
+            .ok()
+            .and_then(|v| v.parse::<i32>().ok())
+            .ok_or(APIError::InvalidRequest(s!("invalid Last-Event-ID header")))?,
+        None => 0,
+    };
+
+    // subscribe before reading stored events, so that none can be missed in between
+    let receiver = state.event_sender.subscribe();
+    let stored_events: Vec<EventResponse> = state
+        .database
+        .iter_events_after_idx(last_event_idx)
+        .await?
+        .into_iter()
+        .map(|e| state.get_event_response(e))
+        .collect();
+    let last_sent_idx = stored_events
+        .last()
+        .map(|e| e.event_idx)
+        .unwrap_or(last_event_idx);
+
+    // stream new events, skipping the ones already sent from the DB
+    let cancel_token = state.cancel_token.clone();
+    let new_events = stream::unfold(
+        (receiver, last_sent_idx),
+        move |(mut receiver, last_sent_idx)| {
+            let cancel_token = cancel_token.clone();
+            async move {
+                loop {
+                    let received = tokio::select! {
+                        _ = cancel_token.cancelled() => return None,
+                        received = receiver.recv() => received,
+                    };
+                    match received {
+                        Ok(event) if event.event_idx <= last_sent_idx => continue,
+                        Ok(event) => {
+                            let event_idx = event.event_idx;
+                            return Some((event, (receiver, event_idx)));
+                        }
+                        // a lagging client gets disconnected and is expected to resume
+                        Err(_) => return None,
+                    }
+                }
+            }
+        },
+    );
+
+    let stream = stream::iter(stored_events).chain(new_events).map(|event| {
+        Ok(SseEvent::default()
+            .id(event.event_idx.to_string())
+            .json_data(&event)
+            .expect("event should be serializable"))
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
 pub(crate) async fn get_current_address_indices(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<GetCurrentAddressIndicesResponse>, APIError> {
@@ -391,25 +1467,199 @@ pub(crate) async fn get_operation_by_idx(
         AuthenticatedUser::WatchOnly => None,
     };
 
-    // get operation response
-    let operation_response = state
-        .get_operation_by_idx_with_files(req.operation_idx, cosigner_idx)
+    // check if request is valid
+    let wait = req.wait.unwrap_or(0);
+    if wait > MAX_WAIT_SECS {
+        return Err(APIError::InvalidRequest(format!(
+            "wait cannot exceed {MAX_WAIT_SECS} seconds"
+        )));
+    }
+    let deadline = Instant::now() + Duration::from_secs(wait as u64);
+
+    loop {
+        // register for notifications before reading, so that no change can be missed in between
+        let notified = state.operation_notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        // get operation response
+        let operation_response = state
+            .get_operation_by_idx_with_files(req.operation_idx, cosigner_idx)
+            .await?;
+
+        // return it unless it's missing or unchanged and there's still time to wait
+        let unchanged = match &operation_response {
+            Some(op) => req.status == Some(op.status),
+            None => true,
+        };
+        if !unchanged || Instant::now() >= deadline {
+            return Ok(Json(operation_response));
+        }
+        tokio::select! {
+            _ = &mut notified => {}
+            _ = tokio::time::sleep_until(deadline) => return Ok(Json(operation_response)),
+            _ = state.cancel_token.cancelled() => return Ok(Json(operation_response)),
+        }
+    }
+}
+
+pub(crate) async fn info(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<InfoResponse>, APIError> {
+    // get last operation index
+    let last_operation_idx = state.database.get_last_operation_idx().await?;
+
+    // get cosigners info, sorted by cosigner index
+    let mut cosigner_idxs: Vec<&i32> = state.cosigners_by_idx.keys().collect();
+    cosigner_idxs.sort();
+    let cosigners = cosigner_idxs
+        .into_iter()
+        .map(|idx| {
+            let key = state.cosigner_keys_by_idx[idx];
+            CosignerInfo {
+                xpub: state.cosigners_by_idx[idx].clone(),
+                fingerprint: key.fingerprint().to_string(),
+                weight: state.cosigner_weights_by_idx[idx],
+            }
+        })
+        .collect();
+
+    // get the addresses in the allowlist, in insertion order
+    let address_allowlist = state
+        .database
+        .iter_allowlist_addresses()
+        .await?
+        .into_iter()
+        .map(|a| a.address)
+        .collect();
+
+    // get the usage of the spending limits, in configuration order
+    let spending_limits = state.get_spending_limits_usage().await?;
+
+    // get the auto-approved operation types, sorted by type
+    let mut auto_approved_ops = state.auto_approved_ops.clone();
+    auto_approved_ops.sort_by_key(|t| *t as u8);
+
+    Ok(Json(InfoResponse {
+        min_rgb_lib_version: MIN_RGB_LIB_VERSION.to_string(),
+        max_rgb_lib_version: MAX_RGB_LIB_VERSION.to_string(),
+        rgb_lib_version: state.rgb_lib_version.clone(),
+        last_operation_idx,
+        network: state.network,
+        cosigners,
+        auto_approved_ops,
+        address_allowlist,
+        spending_limits,
+    }))
+}
+
+pub(crate) async fn list_operations(
+    State(state): State<Arc<AppState>>,
+    user: AuthenticatedUser,
+    WithRejection(Json(req), _): WithRejection<Json<ListOperationsRequest>, APIError>,
+) -> Result<Json<ListOperationsResponse>, APIError> {
+    // get cosigner index, if any
+    let cosigner_idx = match user {
+        AuthenticatedUser::Cosigner(AuthenticatedCosigner { idx, .. }) => Some(idx),
+        AuthenticatedUser::WatchOnly => None,
+    };
+
+    // check if request is valid
+    if req.limit == 0 || req.limit > MAX_LIST_OPERATIONS_LIMIT {
+        return Err(APIError::InvalidRequest(format!(
+            "limit must be between 1 and {MAX_LIST_OPERATIONS_LIMIT}"
+        )));
+    }
+    let initiator_idx = if let Some(xpub) = &req.initiator_xpub {
+        Some(
+            *state
+                .cosigners_by_xpub
+                .get(xpub)
+                .ok_or(APIError::InvalidRequest(s!("unknown initiator xPub")))?,
+        )
+    } else {
+        None
+    };
+    let unprocessed_by = if req.unprocessed_by_me {
+        Some(cosigner_idx.ok_or(APIError::InvalidRequest(s!(
+            "unprocessed_by_me requires a cosigner token"
+        )))?)
+    } else {
+        None
+    };
+
+    // get one more operation than requested to know if there's a next page
+    let filter = OperationFilter {
+        after_idx: req.cursor,
+        status: req.status,
+        r#type: req.operation_type,
+        initiator_idx,
+        created_from: req.created_from,
+        created_to: req.created_to,
+        unprocessed_by,
+    };
+    let mut ops = state
+        .database
+        .iter_operations(&filter, Some(req.limit as u64 + 1))
+        .await?;
+    let next_cursor = if ops.len() > req.limit as usize {
+        ops.truncate(req.limit as usize);
+        ops.last().map(|op| op.idx)
+    } else {
+        None
+    };
+
+    // get operation responses
+    let operations = state
+        .get_operation_responses(ops, cosigner_idx, None)
         .await?;
 
-    Ok(Json(operation_response))
+    Ok(Json(ListOperationsResponse {
+        operations,
+        next_cursor,
+    }))
 }
 
-pub(crate) async fn info(
+pub(crate) async fn list_webhook_deliveries(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<InfoResponse>, APIError> {
-    // get last operation index
-    let last_operation_idx = state.database.get_last_operation_idx().await?;
+    WithRejection(Json(req), _): WithRejection<Json<ListWebhookDeliveriesRequest>, APIError>,
+) -> Result<Json<ListWebhookDeliveriesResponse>, APIError> {
+    // check if request is valid
+    if req.limit == 0 || req.limit > MAX_LIST_WEBHOOK_DELIVERIES_LIMIT {
+        return Err(APIError::InvalidRequest(format!(
+            "limit must be between 1 and {MAX_LIST_WEBHOOK_DELIVERIES_LIMIT}"
+        )));
+    }
 
-    Ok(Json(InfoResponse {
-        min_rgb_lib_version: MIN_RGB_LIB_VERSION.to_string(),
-        max_rgb_lib_version: MAX_RGB_LIB_VERSION.to_string(),
-        rgb_lib_version: state.rgb_lib_version.clone(),
-        last_operation_idx,
+    // get webhook deliveries, optionally filtered by status, getting one more than requested to
+    // know if there's a next page
+    let mut deliveries = state
+        .database
+        .iter_webhook_deliveries(req.status, req.cursor, Some(req.limit as u64 + 1))
+        .await?;
+    let next_cursor = if deliveries.len() > req.limit as usize {
+        deliveries.truncate(req.limit as usize);
+        deliveries.last().map(|d| d.idx)
+    } else {
+        None
+    };
+    let deliveries = deliveries
+        .into_iter()
+        .map(|d| WebhookDeliveryResponse {
+            delivery_idx: d.idx,
+            event_idx: d.event_idx,
+            url: d.url,
+            status: d.status,
+            attempts: d.attempts,
+            next_attempt_at: d.next_attempt_at,
+            last_error: d.last_error,
+            delivered_at: d.delivered_at,
+        })
+        .collect();
+
+    Ok(Json(ListWebhookDeliveriesResponse {
+        deliveries,
+        next_cursor,
     }))
 }
 
@@ -427,7 +1677,7 @@ pub(crate) async fn mark_operation_processed(
         // check if request is allowed
         let op = state
             .database
-            .get_operation_by_idx(req.operation_idx)
+            .get_operation_by_idx(req.operation_idx, None)
             .await?
             .ok_or(APIError::OperationNotFound)?;
         if op.status == OperationStatus::Pending {
@@ -435,6 +1685,11 @@ pub(crate) async fn mark_operation_processed(
                 "a pending operation cannot be marked as processed"
             )));
         }
+        if op.status == OperationStatus::ApprovedPendingDelay {
+            return Err(APIError::CannotMarkOperationProcessed(s!(
+                "an operation in its approval delay cannot be marked as processed"
+            )));
+        }
         let status = state
             .database
             .get_cosigner_op_status_entry(cosigner_idx, req.operation_idx)
@@ -446,13 +1701,89 @@ pub(crate) async fn mark_operation_processed(
             )));
         }
 
-        // set processed_at for cosigner op status entry
+        // check if the reported result is valid and allowed
+        let result = if let Some(result) = req.result {
+            if op.status != OperationStatus::Approved {
+                return Err(APIError::CannotMarkOperationProcessed(s!(
+                    "a result can only be reported for an approved operation"
+                )));
+            }
+            let txid = if let Some(txid) = result.txid {
+                Some(
+                    Txid::from_str(&txid)
+                        .map_err(|_| APIError::InvalidRequest(format!("invalid txid '{txid}'")))?
+                        .to_string(),
+                )
+            } else {
+                None
+            };
+            let state_hash = result.state_hash.to_lowercase();
+            if hex_str_to_vec(&state_hash).is_none_or(|h| h.len() != 32) {
+                return Err(APIError::InvalidRequest(s!(
+                    "state hash must be a 32-byte hex string"
+                )));
+            }
+            Some(ProcessingResult {
+                success: result.success,
+                txid,
+                state_hash,
+            })
+        } else {
+            None
+        };
+
+        // request is valid and allowed, start transaction
+        let txn = state.database.begin_transaction().await?;
+
+        // set processed_at and the result, if any, for cosigner op status entry
         let mut status: cosigner_op_status::ActiveModel = status.into();
         status.processed_at = ActiveValue::Set(Some(now().unix_timestamp()));
+        if let Some(result) = &result {
+            status.result_success = ActiveValue::Set(Some(result.success));
+            status.result_txid = ActiveValue::Set(result.txid.clone());
+            status.result_state_hash = ActiveValue::Set(Some(result.state_hash.clone()));
+        }
         state
             .database
-            .update_cosigner_op_status(status, None)
+            .update_cosigner_op_status(status, Some(&txn))
             .await?;
+        let mut events = vec![
+            state
+                .record_event(
+                    EventType::CosignerProcessed,
+                    req.operation_idx,
+                    Some(cosigner_idx),
+                    &txn,
+                )
+                .await?,
+        ];
+
+        // flag the operation as divergent if cosigners reported different results
+        if result.is_some() && !op.divergent {
+            let results: HashSet<ProcessingResult> = state
+                .database
+                .iter_cosigner_op_status_by_operation_idx(op.idx, &txn)
+                .await?
+                .iter()
+                .filter_map(get_processing_result)
+                .collect();
+            if results.len() > 1 {
+                tracing::warn!("Operation {} diverged: {:?}", op.idx, results);
+                let operation_idx = op.idx;
+                let mut operation: operation::ActiveModel = op.into();
+                operation.divergent = ActiveValue::Set(true);
+                state.database.update_operation(operation, &txn).await?;
+                events.push(
+                    state
+                        .record_event(EventType::OperationDiverged, operation_idx, None, &txn)
+                        .await?,
+                );
+            }
+        }
+
+        // commit transaction and notify subscribers
+        txn.commit().await?;
+        state.publish_events(events);
 
         Ok(Json(EmptyResponse {}))
     })
@@ -488,6 +1819,7 @@ pub(crate) async fn post_operation(
 
         // parse multipart form
         let mut operation_type = None;
+        let mut allowlist_update = None;
         let mut files = Vec::new();
         let mut psbt_file = None;
         while let Some(mut field) = multipart
@@ -504,6 +1836,15 @@ pub(crate) async fn post_operation(
                     let op_type = OperationType::try_from(op_type[0])?;
                     operation_type = Some(op_type);
                 }
+                "allowlist_update" => {
+                    let json_str = field.text().await.map_err(|e| {
+                        APIError::InvalidRequest(format!("failed to read field: {e}"))
+                    })?;
+                    let update: AllowlistUpdate = serde_json::from_str(&json_str).map_err(|e| {
+                        APIError::InvalidRequest(format!("failed to parse JSON: {e}"))
+                    })?;
+                    allowlist_update = Some(update);
+                }
                 field_name if field_name.starts_with("file_") => {
                     let file_type = match &field_name[5..] {
                         "psbt" => FileType::Psbt,
@@ -554,11 +1895,107 @@ pub(crate) async fn post_operation(
         }
 
         // check if request is valid
-        if files.is_empty() && psbt_file.is_none() {
-            return Err(APIError::InvalidRequest(s!("no files nor PSBT provided")));
-        }
         let operation_type =
             operation_type.ok_or(APIError::InvalidRequest(s!("operation type not provided")))?;
+        let address_allowlist = state.get_address_allowlist().await?;
+        let mut allowlist_updates = Vec::new();
+        if operation_type == OperationType::UpdateAllowlist {
+            if !files.is_empty() || psbt_file.is_some() {
+                return Err(APIError::InvalidRequest(s!(
+                    "files are not allowed when updating the allowlist"
+                )));
+            }
+            let update = allowlist_update.ok_or(APIError::InvalidRequest(s!(
+                "allowlist update not provided"
+            )))?;
+            if update.add.is_empty() && update.remove.is_empty() {
+                return Err(APIError::InvalidRequest(s!("empty allowlist update")));
+            }
+            let mut seen = HashSet::new();
+            for (address, add) in update
+                .add
+                .iter()
+                .map(|a| (a, true))
+                .chain(update.remove.iter().map(|a| (a, false)))
+            {
+                let address = parse_address(address, state.network)?;
+                if !seen.insert(address.clone()) {
+                    return Err(APIError::InvalidRequest(format!(
+                        "'{address}' appears more than once"
+                    )));
+                }
+                if add && address_allowlist.contains(&address) {
+                    return Err(APIError::InvalidRequest(format!(
+                        "'{address}' is already in the allowlist"
+                    )));
+                }
+                if !add && !address_allowlist.contains(&address) {
+                    return Err(APIError::InvalidRequest(format!(
+                        "'{address}' is not in the allowlist"
+                    )));
+                }
+                allowlist_updates.push((address, add));
+            }
+        } else {
+            if allowlist_update.is_some() {
+                return Err(APIError::InvalidRequest(s!(
+                    "allowlist update is only allowed when updating the allowlist"
+                )));
+            }
+            if files.is_empty() && psbt_file.is_none() {
+                return Err(APIError::InvalidRequest(s!("no files nor PSBT provided")));
+            }
+        }
+        let mut psbt = None;
+        let mut psbt_summary = None;
+        if let Some(psbt_temp) = &psbt_file {
+            let initiator_psbt = read_psbt(psbt_temp.path()).await?;
+            check_psbt_network(&initiator_psbt, state.network)?;
+            psbt_summary = Some(state.summarize_psbt(&initiator_psbt));
+            psbt = Some(initiator_psbt);
+        }
+
+        // check that outgoing payments only go to allowlisted addresses, if enforced
+        if state.enforce_address_allowlist
+            && [OperationType::SendBtc, OperationType::SendRgb].contains(&operation_type)
+        {
+            let summary = psbt_summary.as_ref().ok_or(APIError::RejectedByPolicy(s!(
+                "a PSBT is required to check the address allowlist"
+            )))?;
+            if let Some(output_idx) = find_non_allowlisted_output(summary, &address_allowlist) {
+                return Err(APIError::RejectedByPolicy(format!(
+                    "output {output_idx} pays outside the address allowlist"
+                )));
+            }
+        }
+
+        // check the operation against the policies and the spending limits to get its threshold
+        let spending_limits_usage = state.get_spending_limits_usage().await?;
+        let threshold = state
+            .get_operation_type_threshold(&operation_type)
+            .map(|threshold| {
+                let threshold = apply_policies(
+                    &state.policies,
+                    &address_allowlist,
+                    operation_type,
+                    psbt_summary.as_ref(),
+                    threshold,
+                )?;
+                // allowlist updates don't move funds, otherwise the outflow is unknown without a
+                // PSBT
+                let outflow = if operation_type == OperationType::UpdateAllowlist {
+                    Some(0)
+                } else {
+                    psbt_summary.as_ref().and_then(|s| s.outflow())
+                };
+                apply_spending_limits(
+                    &state.spending_limits,
+                    &spending_limits_usage,
+                    outflow,
+                    threshold,
+                )
+            })
+            .transpose()?;
 
         // get current timestamp
         let now = now().unix_timestamp();
@@ -567,20 +2004,40 @@ pub(crate) async fn post_operation(
         let txn = state.database.begin_transaction().await?;
 
         // save operation
-        let initial_status = if AUTO_APPROVED_OPS.contains(&operation_type) {
+        let initial_status = if state.auto_approved_ops.contains(&operation_type) {
             OperationStatus::Approved
         } else {
             OperationStatus::Pending
         };
-        let db_operation = operation::ActiveModel {
-            r#type: ActiveValue::Set(operation_type),
-            status: ActiveValue::Set(initial_status),
-            initiator_idx: ActiveValue::Set(cosigner_idx),
-            created_at: ActiveValue::Set(now),
-            ..Default::default()
-        };
+        let db_operation =
+            operation::ActiveModel {
+                r#type: ActiveValue::Set(operation_type),
+                status: ActiveValue::Set(initial_status),
+                initiator_idx: ActiveValue::Set(cosigner_idx),
+                created_at: ActiveValue::Set(now),
+                threshold: ActiveValue::Set(threshold),
+                divergent: ActiveValue::Set(false),
+                psbt_summary: ActiveValue::Set(psbt_summary.as_ref().map(|s| {
+                    serde_json::to_string(s).expect("PSBT summary should be serializable")
+                })),
+                ..Default::default()
+            };
         let operation_idx = state.database.set_operation(db_operation, &txn).await?;
 
+        // save the proposed allowlist update, applied once the operation is approved
+        if !allowlist_updates.is_empty() {
+            let updates = allowlist_updates
+                .into_iter()
+                .map(|(address, add)| allowlist_update::ActiveModel {
+                    operation_idx: ActiveValue::Set(operation_idx),
+                    address: ActiveValue::Set(address),
+                    add: ActiveValue::Set(add),
+                    ..Default::default()
+                })
+                .collect();
+            state.database.set_allowlist_updates(updates, &txn).await?;
+        }
+
         // save operation files
         for (file_type, temp_file) in files.into_iter() {
             let file_id = compute_file_id(temp_file.path()).await?;
@@ -588,10 +2045,12 @@ pub(crate) async fn post_operation(
             if !file_path.exists() {
                 persist_temp_file(temp_file, &file_path).await?;
             }
+            let size_bytes = tokio::fs::metadata(&file_path).await?.len();
             let db_file = op_file::ActiveModel {
                 file_id: ActiveValue::Set(file_id),
                 r#type: ActiveValue::Set(file_type),
                 operation_idx: ActiveValue::Set(operation_idx),
+                size_bytes: ActiveValue::Set(Some(size_bytes as i64)),
                 ..Default::default()
             };
             state.database.set_op_file(db_file, &txn).await?;
@@ -604,10 +2063,12 @@ pub(crate) async fn post_operation(
             if !file_path.exists() {
                 persist_temp_file(psbt_temp, &file_path).await?;
             }
+            let size_bytes = tokio::fs::metadata(&file_path).await?.len();
             let db_file = op_file::ActiveModel {
                 file_id: ActiveValue::Set(file_id),
                 r#type: ActiveValue::Set(FileType::Psbt),
                 operation_idx: ActiveValue::Set(operation_idx),
+                size_bytes: ActiveValue::Set(Some(size_bytes as i64)),
                 ..Default::default()
             };
             Some(state.database.set_op_file(db_file, &txn).await?)
@@ -635,9 +2096,58 @@ pub(crate) async fn post_operation(
                 .set_cosigner_op_status(&cosigner_op_status, &txn)
                 .await?;
         }
+        let cosigner_response = cosigner_response::ActiveModel {
+            operation_idx: ActiveValue::Set(operation_idx),
+            cosigner_idx: ActiveValue::Set(cosigner_idx),
+            ack: ActiveValue::Set(true),
+            psbt_op_file_idx: ActiveValue::Set(psbt_op_file_idx),
+            responded_at: ActiveValue::Set(now),
+            ..Default::default()
+        };
+        state
+            .database
+            .set_cosigner_response(cosigner_response, &txn)
+            .await?;
+
+        // record events
+        let mut events = vec![
+            state
+                .record_event(
+                    EventType::OperationPosted,
+                    operation_idx,
+                    Some(cosigner_idx),
+                    &txn,
+                )
+                .await?,
+        ];
+        if initial_status == OperationStatus::Approved {
+            if let Some(psbt_summary) = &psbt_summary {
+                state
+                    .record_outflow(operation_idx, psbt_summary, &txn)
+                    .await?;
+            }
+            events.push(
+                state
+                    .record_event(EventType::OperationApproved, operation_idx, None, &txn)
+                    .await?,
+            );
+        } else {
+            // the initiator's ACK alone may be enough to approve the operation
+            let op = state
+                .database
+                .get_operation_by_idx(operation_idx, Some(&txn))
+                .await?
+                .expect("operation should exist after saving it");
+            events.extend(
+                state
+                    .update_operation_status(op, psbt.map(|psbt| (cosigner_idx, psbt)), &txn)
+                    .await?,
+            );
+        }
 
-        // commit transaction
+        // commit transaction and notify subscribers
         txn.commit().await?;
+        state.publish_events(events);
 
         Ok(Json(PostOperationResponse { operation_idx }))
     })
@@ -706,14 +2216,20 @@ pub(crate) async fn respond_to_operation(
 
         // check if request is valid and allowed
         let req = req.ok_or(APIError::InvalidRequest(s!("missing request body")))?;
-        if req.ack && psbt_file.is_none() {
-            return Err(APIError::InvalidRequest(s!("ACK requires PSBT file")));
-        }
         let op = state
             .database
-            .get_operation_by_idx(req.operation_idx)
+            .get_operation_by_idx(req.operation_idx, None)
             .await?
             .ok_or(APIError::OperationNotFound)?;
+        if op.r#type == OperationType::UpdateAllowlist {
+            if psbt_file.is_some() {
+                return Err(APIError::InvalidRequest(s!(
+                    "PSBT file is not allowed when updating the allowlist"
+                )));
+            }
+        } else if req.ack && psbt_file.is_none() {
+            return Err(APIError::InvalidRequest(s!("ACK requires PSBT file")));
+        }
         if op.initiator_idx == cosigner_idx {
             return Err(APIError::CannotRespondToOperation(s!(
                 "cannot respond to your own operation"
@@ -724,16 +2240,16 @@ pub(crate) async fn respond_to_operation(
                 "operation is not pending"
             )));
         }
+        if state.is_operation_expired(&op) {
+            return Err(APIError::CannotRespondToOperation(s!(
+                "operation has expired"
+            )));
+        }
         let status_entry = state
             .database
             .get_cosigner_op_status_entry(cosigner_idx, req.operation_idx)
             .await?
             .expect("CosignerOpStatus entry should exist");
-        if status_entry.ack.is_some() {
-            return Err(APIError::CannotRespondToOperation(s!(
-                "already responded to this operation"
-            )));
-        }
         let last_processed_op_idx = state
             .database
             .get_last_cosigner_processed_op_idx(cosigner_idx)
@@ -743,6 +2259,30 @@ pub(crate) async fn respond_to_operation(
                 "operation is not the next one to be processed"
             )));
         }
+        let mut ack_psbt = None;
+        if let Some(psbt_temp) = &psbt_file {
+            let psbt = read_psbt(psbt_temp.path()).await?;
+            check_psbt_network(&psbt, state.network)?;
+            // the initiator may post no PSBT, in which case the first response one is the reference
+            if let Some(first_psbt) = state.get_first_psbt(req.operation_idx).await?
+                && psbt.unsigned_tx.compute_txid() != first_psbt.unsigned_tx.compute_txid()
+            {
+                return Err(APIError::InvalidPsbt(s!(
+                    "transaction differs from the first uploaded one"
+                )));
+            }
+            let initiator_psbt = state
+                .get_cosigner_psbt(req.operation_idx, op.initiator_idx)
+                .await?;
+            if req.ack {
+                check_psbt_signatures(
+                    &psbt,
+                    initiator_psbt.as_ref(),
+                    &state.cosigner_keys_by_idx[&cosigner_idx],
+                )?;
+                ack_psbt = Some(psbt);
+            }
+        }
 
         // request is valid and allowed, start transaction
         let txn = state.database.begin_transaction().await?;
@@ -754,10 +2294,12 @@ pub(crate) async fn respond_to_operation(
             if !file_path.exists() {
                 persist_temp_file(psbt_temp, &file_path).await?;
             }
+            let size_bytes = tokio::fs::metadata(&file_path).await?.len();
             let db_file = op_file::ActiveModel {
                 file_id: ActiveValue::Set(file_id),
                 r#type: ActiveValue::Set(FileType::Psbt),
                 operation_idx: ActiveValue::Set(op.idx),
+                size_bytes: ActiveValue::Set(Some(size_bytes as i64)),
                 ..Default::default()
             };
             Some(state.database.set_op_file(db_file, &txn).await?)
@@ -765,62 +2307,370 @@ pub(crate) async fn respond_to_operation(
             None
         };
 
-        // update cosigner op status
+        // update cosigner op status, a revised response replaces the previous one (PSBT included)
+        let responded_at = now().unix_timestamp();
         let mut status: cosigner_op_status::ActiveModel = status_entry.into();
         status.ack = ActiveValue::Set(Some(req.ack));
-        status.responded_at = ActiveValue::Set(Some(now().unix_timestamp()));
-        if let Some(psbt_op_file_idx) = psbt_op_file_idx {
-            status.psbt_op_file_idx = ActiveValue::Set(Some(psbt_op_file_idx));
-        }
+        status.responded_at = ActiveValue::Set(Some(responded_at));
+        status.psbt_op_file_idx = ActiveValue::Set(psbt_op_file_idx);
         state
             .database
             .update_cosigner_op_status(status, Some(&txn))
             .await?;
 
-        // count ACKs and NACKs to determine new status
-        let status_entries = state
+        // keep track of every response given
+        let cosigner_response = cosigner_response::ActiveModel {
+            operation_idx: ActiveValue::Set(op.idx),
+            cosigner_idx: ActiveValue::Set(cosigner_idx),
+            ack: ActiveValue::Set(req.ack),
+            psbt_op_file_idx: ActiveValue::Set(psbt_op_file_idx),
+            responded_at: ActiveValue::Set(responded_at),
+            ..Default::default()
+        };
+        state
             .database
-            .iter_cosigner_op_status_by_operation_idx(op.idx, &txn)
+            .set_cosigner_response(cosigner_response, &txn)
             .await?;
-        let (ack_count, nack_count) =
-            status_entries
-                .iter()
-                .fold((0u8, 0u8), |(ack, nack), s| match s.ack {
-                    Some(true) => (ack + 1, nack),
-                    Some(false) => (ack, nack + 1),
-                    None => (ack, nack),
-                });
-        let threshold = get_threshold_for_operation(
-            &op.r#type,
-            state.threshold_vanilla,
-            state.threshold_colored,
-        )
-        .expect("threshold should be set for non-auto-approved operations");
-        let total_cosigners = state.cosigners_by_xpub.len() as u8;
-        let new_status = if ack_count >= threshold {
-            Some(OperationStatus::Approved)
-        } else if nack_count > (total_cosigners - threshold) {
-            Some(OperationStatus::Discarded)
+        let mut events = vec![
+            state
+                .record_event(
+                    EventType::CosignerResponded,
+                    op.idx,
+                    Some(cosigner_idx),
+                    &txn,
+                )
+                .await?,
+        ];
+
+        // update operation status if the operation is now approved (possibly after a delay) or
+        // discarded
+        events.extend(
+            state
+                .update_operation_status(op, ack_psbt.map(|psbt| (cosigner_idx, psbt)), &txn)
+                .await?,
+        );
+
+        // commit transaction and notify subscribers
+        txn.commit().await?;
+        state.publish_events(events);
+
+        // get updated operation response
+        let operation_response = state
+            .get_operation_by_idx_with_files(req.operation_idx, Some(cosigner_idx))
+            .await?
+            .expect("operation should exist after response");
+
+        Ok(Json(operation_response))
+    })
+    .await
+}
+
+pub(crate) async fn revoke_token(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedCosigner {
+        idx: cosigner_idx,
+        xpub,
+    }: AuthenticatedCosigner,
+    WithRejection(Json(req), _): WithRejection<Json<RevokeTokenRequest>, APIError>,
+) -> Result<Json<EmptyResponse>, APIError> {
+    no_cancel(async move {
+        // acquire write lock to prevent concurrent write operations
+        let _lock = state.write_lock.lock().await;
+
+        // check if request is valid
+        let token =
+            Biscuit::from_base64(req.token.trim(), state.root_public_key).map_err(|_| {
+                APIError::InvalidToken(s!("not a valid token issued with the root key"))
+            })?;
+
+        // check if request is allowed, cosigners can only revoke their own tokens
+        let token_xpub = get_token_role_and_xpub(&token).and_then(|(_, xpub)| xpub);
+        if token_xpub.as_ref() != Some(&xpub) {
+            return Err(APIError::CannotRevokeToken(s!(
+                "token doesn't belong to the requesting cosigner"
+            )));
+        }
+        let revocation_id = hex::encode(&token.revocation_identifiers()[0]);
+        if state
+            .database
+            .get_revoked_token(&revocation_id)
+            .await?
+            .is_some()
+        {
+            return Err(APIError::CannotRevokeToken(s!("token already revoked")));
+        }
+
+        // save revocation ID
+        let db_revoked_token = revoked_token::ActiveModel {
+            revocation_id: ActiveValue::Set(revocation_id.clone()),
+            revoked_at: ActiveValue::Set(now().unix_timestamp()),
+            ..Default::default()
+        };
+        state.database.set_revoked_token(db_revoked_token).await?;
+        tracing::info!("cosigner {cosigner_idx} revoked token with ID {revocation_id}");
+
+        Ok(Json(EmptyResponse {}))
+    })
+    .await
+}
+
+pub(crate) async fn sync_operations(
+    State(state): State<Arc<AppState>>,
+    user: AuthenticatedUser,
+    WithRejection(Json(req), _): WithRejection<Json<SyncOperationsRequest>, APIError>,
+) -> Result<Response, APIError> {
+    // get cosigner index, if any
+    let cosigner_idx = match user {
+        AuthenticatedUser::Cosigner(AuthenticatedCosigner { idx, .. }) => Some(idx),
+        AuthenticatedUser::WatchOnly => None,
+    };
+
+    // get the operations after the given index, up to the maximum, getting one more to know if
+    // there are others
+    let filter = OperationFilter {
+        after_idx: Some(req.after_idx),
+        ..Default::default()
+    };
+    let mut ops = state
+        .database
+        .iter_operations(&filter, Some(MAX_SYNC_OPERATIONS as u64 + 1))
+        .await?;
+    let has_more = ops.len() > MAX_SYNC_OPERATIONS as usize;
+    ops.truncate(MAX_SYNC_OPERATIONS as usize);
+    let operations = state
+        .get_operation_responses(ops, cosigner_idx, None)
+        .await?;
+    let sync_response = SyncOperationsResponse {
+        operations,
+        has_more,
+    };
+    if !req.archive {
+        return Ok(Json(sync_response).into_response());
+    }
+
+    // stream a tar archive with the operations and the files they reference, each file once
+    let mtime = now().unix_timestamp() as u64;
+    let json = serde_json::to_vec(&sync_response).expect("response should be serializable");
+    let json_len = json.len() as u64;
+    let mut content_length = tar_entry_len(json_len);
+    let mut entries: Vec<BoxStream<'static, Result<Bytes, std::io::Error>>> = vec![
+        stream::iter([
+            tar_entry_header(SYNC_ARCHIVE_OPERATIONS_PATH, json_len, mtime)?,
+            json,
+            tar_entry_padding(json_len),
+        ])
+        .map(|chunk| Ok(Bytes::from(chunk)))
+        .boxed(),
+    ];
+    let mut file_ids = HashSet::new();
+    for file in sync_response.operations.iter().flat_map(|o| &o.files) {
+        if !file_ids.insert(file.file_id.clone()) {
+            continue;
+        }
+        // stat the file upfront, so a missing one fails the request instead of corrupting the
+        // archive after its length has been sent
+        let file_path = state.files_dir.join(&file.file_id);
+        let size_bytes = tokio::fs::metadata(&file_path).await?.len();
+        let entry_header = tar_entry_header(
+            &format!("{SYNC_ARCHIVE_FILES_DIR}/{}", file.file_id),
+            size_bytes,
+            mtime,
+        )?;
+        let content = stream::once(File::open(file_path))
+            .map_ok(ReaderStream::new)
+            .try_flatten();
+        let padding = tar_entry_padding(size_bytes);
+        content_length += tar_entry_len(size_bytes);
+        entries.push(
+            stream::once(async { Ok(Bytes::from(entry_header)) })
+                .chain(content)
+                .chain(stream::once(async { Ok(Bytes::from(padding)) }))
+                .boxed(),
+        );
+    }
+    let end = tar_end();
+    content_length += end.len() as u64;
+    entries.push(stream::once(async { Ok(Bytes::from(end)) }).boxed());
+
+    let body = Body::from_stream(stream::iter(entries).flatten());
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_LENGTH,
+        HeaderValue::from_str(&content_length.to_string()).expect("cannot be invalid"),
+    );
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-tar"),
+    );
+
+    Ok((StatusCode::OK, headers, body).into_response())
+}
+
+pub(crate) async fn update_chain_status(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedCosigner {
+        idx: cosigner_idx, ..
+    }: AuthenticatedCosigner,
+    WithRejection(Json(req), _): WithRejection<Json<UpdateChainStatusRequest>, APIError>,
+) -> Result<Json<OperationResponse>, APIError> {
+    no_cancel(async move {
+        // acquire write lock to prevent concurrent write operations
+        let _lock = state.write_lock.lock().await;
+
+        // check if request is valid
+        let txid = if let Some(txid) = req.txid {
+            Some(
+                Txid::from_str(&txid)
+                    .map_err(|_| APIError::InvalidRequest(format!("invalid txid '{txid}'")))?
+                    .to_string(),
+            )
         } else {
             None
         };
+        match req.chain_status {
+            ChainStatus::Broadcast if txid.is_none() => {
+                return Err(APIError::InvalidRequest(s!(
+                    "a txid is required to report a broadcast"
+                )));
+            }
+            ChainStatus::Confirmed if req.block_height.is_none() => {
+                return Err(APIError::InvalidRequest(s!(
+                    "a block height is required to report a confirmation"
+                )));
+            }
+            ChainStatus::Broadcast | ChainStatus::Failed if req.block_height.is_some() => {
+                return Err(APIError::InvalidRequest(s!(
+                    "a block height can only be reported with a confirmation"
+                )));
+            }
+            _ => {}
+        }
 
-        // update operation status if the operation is now approved or discarded
-        if let Some(status) = new_status {
-            let mut operation: operation::ActiveModel = op.into();
-            operation.status = ActiveValue::Set(status);
-            state.database.update_operation(operation, &txn).await?;
-            tracing::debug!("Operation new status: {:?}", new_status);
+        // check if request is allowed
+        let op = state
+            .database
+            .get_operation_by_idx(req.operation_idx, None)
+            .await?
+            .ok_or(APIError::OperationNotFound)?;
+        if op.status != OperationStatus::Approved {
+            return Err(APIError::CannotUpdateChainStatus(s!(
+                "only approved operations can be tracked on chain"
+            )));
+        }
+        // the chain status can only move forward: broadcast, then either confirmed or failed
+        if let Some(current) = op.chain_status
+            && (current != ChainStatus::Broadcast || req.chain_status == ChainStatus::Broadcast)
+        {
+            return Err(APIError::CannotUpdateChainStatus(format!(
+                "operation is already {current:?}"
+            )));
         }
+        let txid = match (&op.txid, txid) {
+            (Some(known), Some(reported)) if *known != reported => {
+                return Err(APIError::CannotUpdateChainStatus(s!(
+                    "txid differs from the one already reported"
+                )));
+            }
+            (known, reported) => reported.or(known.clone()),
+        };
+        if req.chain_status == ChainStatus::Confirmed && txid.is_none() {
+            return Err(APIError::InvalidRequest(s!(
+                "a txid is required to report a confirmation"
+            )));
+        }
+
+        // request is valid and allowed, start transaction
+        let txn = state.database.begin_transaction().await?;
+
+        // update operation chain status
+        let event = state
+            .update_chain_status(
+                op,
+                req.chain_status,
+                txid,
+                req.block_height,
+                Some(cosigner_idx),
+                &txn,
+            )
+            .await?;
+        tracing::info!(
+            "Operation {} chain status set to {:?}",
+            req.operation_idx,
+            req.chain_status
+        );
 
-        // commit transaction
+        // commit transaction and notify subscribers
         txn.commit().await?;
+        state.publish_events(vec![event]);
 
         // get updated operation response
         let operation_response = state
             .get_operation_by_idx_with_files(req.operation_idx, Some(cosigner_idx))
             .await?
-            .expect("operation should exist after response");
+            .expect("operation should exist after chain status update");
+
+        Ok(Json(operation_response))
+    })
+    .await
+}
+
+pub(crate) async fn veto_operation(
+    State(state): State<Arc<AppState>>,
+    AuthenticatedCosigner {
+        idx: cosigner_idx, ..
+    }: AuthenticatedCosigner,
+    WithRejection(Json(req), _): WithRejection<Json<VetoOperationRequest>, APIError>,
+) -> Result<Json<OperationResponse>, APIError> {
+    no_cancel(async move {
+        // acquire write lock to prevent concurrent write operations
+        let _lock = state.write_lock.lock().await;
+
+        // check if request is allowed
+        let op = state
+            .database
+            .get_operation_by_idx(req.operation_idx, None)
+            .await?
+            .ok_or(APIError::OperationNotFound)?;
+        if op.status != OperationStatus::ApprovedPendingDelay {
+            return Err(APIError::CannotVetoOperation(s!(
+                "operation is not in its approval delay"
+            )));
+        }
+        if op
+            .delay_ends_at
+            .is_some_and(|delay_ends_at| now().unix_timestamp() >= delay_ends_at)
+        {
+            return Err(APIError::CannotVetoOperation(s!(
+                "approval delay has already ended"
+            )));
+        }
+
+        // request is allowed, start transaction
+        let txn = state.database.begin_transaction().await?;
+
+        // update operation status
+        let mut operation: operation::ActiveModel = op.into();
+        operation.status = ActiveValue::Set(OperationStatus::Vetoed);
+        state.database.update_operation(operation, &txn).await?;
+        tracing::debug!("Operation {} vetoed", req.operation_idx);
+        let event = state
+            .record_event(
+                EventType::OperationVetoed,
+                req.operation_idx,
+                Some(cosigner_idx),
+                &txn,
+            )
+            .await?;
+
+        // commit transaction and notify subscribers
+        txn.commit().await?;
+        state.publish_events(vec![event]);
+
+        // get updated operation response
+        let operation_response = state
+            .get_operation_by_idx_with_files(req.operation_idx, Some(cosigner_idx))
+            .await?
+            .expect("operation should exist after veto");
 
         Ok(Json(operation_response))
     })
@@ -863,7 +2713,11 @@ mod tests {
         ));
         assert!(matches!(
             OperationType::try_from(8),
-            Err(APIError::InvalidOperationType(8))
+            Ok(OperationType::UpdateAllowlist)
+        ));
+        assert!(matches!(
+            OperationType::try_from(9),
+            Err(APIError::InvalidOperationType(9))
         ));
     }
 }
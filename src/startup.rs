@@ -9,21 +9,31 @@ use std::{
 
 use amplify::s;
 use biscuit_auth::PublicKey;
+use bitcoin::bip32::Xpub;
 use clap::Parser;
 use migration::{Migrator, MigratorTrait};
 use sea_orm::{ActiveValue, ConnectOptions, Database};
 use serde::{Deserialize, Serialize};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify, broadcast};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
     auth::check_auth_args,
+    chain::EsploraChainSource,
     database::{
         AppDatabase,
         entities::{config, cosigner, next_address_index},
     },
     error::AppError,
-    utils::check_port_is_available,
+    routes::{
+        BitcoinNetwork, DEFAULT_AUTO_APPROVED_OPS, EventResponse, OperationType,
+        SpendingLimitWindow,
+    },
+    tasks::{
+        approve_delayed_operations, deliver_webhooks, expire_pending_operations,
+        track_confirmations,
+    },
+    utils::{check_port_is_available, parse_xpub},
 };
 
 const CONFIG_NAME: &str = "config.toml";
@@ -36,6 +46,14 @@ pub(crate) const FILES_DIR: &str = "files";
 pub(crate) const MIN_RGB_LIB_VERSION: &str = "0.3";
 pub(crate) const MAX_RGB_LIB_VERSION: &str = "0.3";
 
+// number of events buffered for each subscriber before it is considered lagging
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+// seconds between confirmation checks, if not configured
+const DEFAULT_CHAIN_SOURCE_POLL_INTERVAL: u64 = 60;
+// longest pending timeout allowed, in seconds (one year)
+pub(crate) const MAX_PENDING_TIMEOUT: u64 = 365 * 24 * 60 * 60;
+
 pub(crate) const DB_MIN_CONNECTIONS: u32 = 0;
 pub(crate) const DB_TIMEOUT: Duration = Duration::from_secs(8);
 pub(crate) const DB_NAME: &str = "rgb_multisig_bridge_db";
@@ -53,23 +71,123 @@ pub(crate) struct AppArgs {
 
 #[derive(Default, Serialize, Deserialize)]
 pub(crate) struct AppConfig {
+    pub(crate) network: BitcoinNetwork,
     pub(crate) cosigner_xpubs: Vec<String>,
+    #[serde(default)]
+    pub(crate) cosigner_weights: Vec<u8>,
+    #[serde(default)]
+    pub(crate) cosigner_roles: HashMap<String, CosignerRolesConfig>,
     pub(crate) threshold_colored: u8,
     pub(crate) threshold_vanilla: u8,
+    #[serde(default)]
+    pub(crate) thresholds: HashMap<OperationType, u8>,
+    #[serde(default = "default_auto_approved_ops")]
+    pub(crate) auto_approved_ops: Vec<OperationType>,
     pub(crate) root_public_key: String,
     pub(crate) rgb_lib_version: String,
+    pub(crate) pending_timeout: Option<u64>,
+    #[serde(default)]
+    pub(crate) webhooks: Vec<WebhookConfig>,
+    #[serde(default)]
+    pub(crate) enforce_address_allowlist: bool,
+    #[serde(default)]
+    pub(crate) policies: Vec<PolicyConfig>,
+    #[serde(default)]
+    pub(crate) spending_limits: Vec<SpendingLimitConfig>,
+    #[serde(default)]
+    pub(crate) approval_delays: HashMap<OperationType, u64>,
+    pub(crate) chain_source: Option<ChainSourceConfig>,
 }
 
-#[derive(Debug, Parser)]
-#[command(author, version, about, long_about = None)]
+/// The roles held by a cosigner, on top of responding to operations
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct CosignerRolesConfig {
+    /// Whether a NACK from the cosigner discards the operation regardless of the ACKs
+    #[serde(default)]
+    pub(crate) veto: bool,
+}
+
+fn default_auto_approved_ops() -> Vec<OperationType> {
+    DEFAULT_AUTO_APPROVED_OPS.to_vec()
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct WebhookConfig {
+    pub(crate) url: String,
+    pub(crate) secret: String,
+}
+
+/// The chain source used to track the confirmation of broadcast operations
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ChainSourceConfig {
+    /// URL of the Esplora API to query
+    pub(crate) esplora_url: String,
+    /// Number of seconds between confirmation checks
+    #[serde(default = "default_chain_source_poll_interval")]
+    pub(crate) poll_interval: u64,
+}
+
+fn default_chain_source_poll_interval() -> u64 {
+    DEFAULT_CHAIN_SOURCE_POLL_INTERVAL
+}
+
+/// A spending policy, raising the threshold of (or rejecting) the operations meeting all its
+/// conditions
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct PolicyConfig {
+    /// Operation types the policy applies to, all the ones requiring approval if empty
+    #[serde(default)]
+    pub(crate) operation_types: Vec<OperationType>,
+    /// Met if the PSBT sends more than this amount (in sats) to non-change outputs
+    pub(crate) sent_amount_above: Option<u64>,
+    /// Met if the PSBT pays to non-change outputs outside the address allowlist
+    #[serde(default)]
+    pub(crate) non_allowlisted_outputs: bool,
+    /// Met if the PSBT fee rate (in sat/vB) is above this value
+    pub(crate) fee_rate_above: Option<f64>,
+    /// Threshold required by the operations meeting the conditions
+    pub(crate) threshold: Option<u8>,
+    /// Whether the operations meeting the conditions are rejected
+    #[serde(default)]
+    pub(crate) reject: bool,
+}
+
+/// A limit on the amount leaving the multisig in a rolling time window, raising the threshold of
+/// (or rejecting) the operations that would exceed it
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct SpendingLimitConfig {
+    /// Time window the limit applies to
+    pub(crate) window: SpendingLimitWindow,
+    /// Maximum amount (in sats) that can leave the multisig in the window
+    pub(crate) amount_sat: u64,
+    /// Threshold required by the operations exceeding the limit
+    pub(crate) threshold: Option<u8>,
+    /// Whether the operations exceeding the limit are rejected
+    #[serde(default)]
+    pub(crate) reject: bool,
+}
+
+#[derive(Clone, Debug)]
 pub(crate) struct AppParams {
     pub(crate) app_dir: PathBuf,
     pub(crate) daemon_listening_port: u16,
+    pub(crate) network: BitcoinNetwork,
     pub(crate) cosigner_xpubs: Vec<String>,
+    pub(crate) cosigner_weights: Vec<u8>,
+    pub(crate) cosigner_roles: HashMap<String, CosignerRolesConfig>,
     pub(crate) threshold_colored: u8,
     pub(crate) threshold_vanilla: u8,
+    pub(crate) thresholds: HashMap<OperationType, u8>,
+    pub(crate) auto_approved_ops: Vec<OperationType>,
     pub(crate) root_public_key: PublicKey,
     pub(crate) rgb_lib_version: String,
+    pub(crate) pending_timeout: Option<u64>,
+    pub(crate) webhooks: Vec<WebhookConfig>,
+    pub(crate) enforce_address_allowlist: bool,
+    pub(crate) policies: Vec<PolicyConfig>,
+    pub(crate) spending_limits: Vec<SpendingLimitConfig>,
+    pub(crate) approval_delays: HashMap<OperationType, u64>,
+    pub(crate) chain_source: Option<ChainSourceConfig>,
 }
 
 pub(crate) struct AppState {
@@ -77,11 +195,26 @@ pub(crate) struct AppState {
     pub(crate) database: AppDatabase,
     pub(crate) cancel_token: CancellationToken,
     pub(crate) root_public_key: PublicKey,
+    pub(crate) network: BitcoinNetwork,
     pub(crate) cosigners_by_xpub: HashMap<String, i32>,
     pub(crate) cosigners_by_idx: HashMap<i32, String>,
+    pub(crate) cosigner_keys_by_idx: HashMap<i32, Xpub>,
+    pub(crate) cosigner_weights_by_idx: HashMap<i32, u8>,
+    pub(crate) veto_holders: HashSet<i32>,
     pub(crate) threshold_colored: u8,
     pub(crate) threshold_vanilla: u8,
+    pub(crate) thresholds: HashMap<OperationType, u8>,
+    pub(crate) auto_approved_ops: Vec<OperationType>,
     pub(crate) rgb_lib_version: String,
+    pub(crate) pending_timeout: Option<u64>,
+    pub(crate) event_sender: broadcast::Sender<EventResponse>,
+    pub(crate) operation_notify: Notify,
+    pub(crate) webhooks: Vec<WebhookConfig>,
+    pub(crate) webhook_notify: Notify,
+    pub(crate) enforce_address_allowlist: bool,
+    pub(crate) policies: Vec<PolicyConfig>,
+    pub(crate) spending_limits: Vec<SpendingLimitConfig>,
+    pub(crate) approval_delays: HashMap<OperationType, u64>,
     pub(crate) write_lock: Arc<Mutex<()>>,
 }
 
@@ -108,16 +241,48 @@ fn parse_args_and_config_internal(args: AppArgs, cfg: AppConfig) -> Result<AppPa
     if num_cosigners < MIN_COSIGNERS {
         return Err(AppError::InvalidCosignerNumber(num_cosigners));
     }
+    validate_cosigner_xpubs(&cfg.cosigner_xpubs, cfg.network)?;
+    validate_cosigner_roles(&cfg.cosigner_roles, &cfg.cosigner_xpubs)?;
+    let cosigner_weights = if cfg.cosigner_weights.is_empty() {
+        vec![1; num_cosigners]
+    } else {
+        validate_cosigner_weights(&cfg.cosigner_weights, num_cosigners)?;
+        cfg.cosigner_weights
+    };
+    let total_weight = cosigner_weights.iter().map(|w| *w as u32).sum();
     if cfg.threshold_colored == 0 || cfg.threshold_vanilla == 0 {
         return Err(AppError::InvalidThreshold(s!("must be a positive value")));
     }
-    if cfg.threshold_colored as usize > num_cosigners
-        || cfg.threshold_vanilla as usize > num_cosigners
-    {
+    if cfg.threshold_colored as u32 > total_weight || cfg.threshold_vanilla as u32 > total_weight {
         return Err(AppError::InvalidThreshold(s!(
-            "cannot be higher than number of cosigners"
+            "cannot be higher than the total cosigner weight"
         )));
     }
+    validate_auto_approved_ops(
+        &cfg.auto_approved_ops,
+        !cfg.policies.is_empty(),
+        cfg.enforce_address_allowlist,
+    )?;
+    validate_thresholds(&cfg.thresholds, total_weight, &cfg.auto_approved_ops)?;
+
+    if cfg
+        .pending_timeout
+        .is_some_and(|t| t == 0 || t > MAX_PENDING_TIMEOUT)
+    {
+        return Err(AppError::InvalidPendingTimeout);
+    }
+
+    validate_webhooks(&cfg.webhooks)?;
+
+    validate_policies(&cfg.policies, total_weight, &cfg.auto_approved_ops)?;
+
+    validate_spending_limits(&cfg.spending_limits, total_weight, &cfg.auto_approved_ops)?;
+
+    validate_approval_delays(&cfg.approval_delays, &cfg.auto_approved_ops)?;
+
+    if let Some(chain_source) = &cfg.chain_source {
+        validate_chain_source(chain_source)?;
+    }
 
     let root_public_key = check_auth_args(&cfg.root_public_key)?;
 
@@ -131,14 +296,287 @@ fn parse_args_and_config_internal(args: AppArgs, cfg: AppConfig) -> Result<AppPa
     Ok(AppParams {
         app_dir: args.app_directory_path,
         daemon_listening_port,
+        network: cfg.network,
         cosigner_xpubs: cfg.cosigner_xpubs,
+        cosigner_weights,
+        cosigner_roles: cfg.cosigner_roles,
         threshold_colored: cfg.threshold_colored,
         threshold_vanilla: cfg.threshold_vanilla,
+        thresholds: cfg.thresholds,
+        auto_approved_ops: cfg.auto_approved_ops,
         root_public_key,
         rgb_lib_version: cfg.rgb_lib_version,
+        pending_timeout: cfg.pending_timeout,
+        webhooks: cfg.webhooks,
+        enforce_address_allowlist: cfg.enforce_address_allowlist,
+        policies: cfg.policies,
+        spending_limits: cfg.spending_limits,
+        approval_delays: cfg.approval_delays,
+        chain_source: cfg.chain_source,
     })
 }
 
+fn validate_approval_delays(
+    approval_delays: &HashMap<OperationType, u64>,
+    auto_approved_ops: &[OperationType],
+) -> Result<(), AppError> {
+    for (op_type, delay) in approval_delays {
+        if auto_approved_ops.contains(op_type) {
+            return Err(AppError::InvalidApprovalDelay(format!(
+                "cannot delay auto-approved operation type {op_type:?}"
+            )));
+        }
+        if *delay == 0 {
+            return Err(AppError::InvalidApprovalDelay(format!(
+                "delay for {op_type:?} must be a positive number of seconds"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_auto_approved_ops(
+    auto_approved_ops: &[OperationType],
+    has_policies: bool,
+    enforce_address_allowlist: bool,
+) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    for op_type in auto_approved_ops {
+        if *op_type == OperationType::UpdateAllowlist {
+            return Err(AppError::InvalidAutoApprovedOps(s!(
+                "allowlist updates always require approval"
+            )));
+        }
+        // operations moving funds must go through the checks of policies and allowlist
+        if !DEFAULT_AUTO_APPROVED_OPS.contains(op_type) {
+            if has_policies {
+                return Err(AppError::InvalidAutoApprovedOps(format!(
+                    "{op_type:?} cannot be auto-approved when policies are set"
+                )));
+            }
+            if enforce_address_allowlist {
+                return Err(AppError::InvalidAutoApprovedOps(format!(
+                    "{op_type:?} cannot be auto-approved when the address allowlist is enforced"
+                )));
+            }
+        }
+        if !seen.insert(op_type) {
+            return Err(AppError::InvalidAutoApprovedOps(format!(
+                "{op_type:?} is a duplicate"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_chain_source(chain_source: &ChainSourceConfig) -> Result<(), AppError> {
+    let url = reqwest::Url::parse(&chain_source.esplora_url).map_err(|e| {
+        AppError::InvalidChainSource(format!("invalid URL '{}': {e}", chain_source.esplora_url))
+    })?;
+    if !["http", "https"].contains(&url.scheme()) {
+        return Err(AppError::InvalidChainSource(format!(
+            "unsupported URL scheme for '{}'",
+            chain_source.esplora_url
+        )));
+    }
+    if chain_source.poll_interval == 0 {
+        return Err(AppError::InvalidChainSource(s!(
+            "poll interval must be a positive number of seconds"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_cosigner_roles(
+    cosigner_roles: &HashMap<String, CosignerRolesConfig>,
+    cosigner_xpubs: &[String],
+) -> Result<(), AppError> {
+    if let Some(xpub) = cosigner_roles
+        .keys()
+        .find(|xpub| !cosigner_xpubs.contains(xpub))
+    {
+        return Err(AppError::InvalidCosignerRole(format!(
+            "'{xpub}' is not a cosigner xPub"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_cosigner_weights(
+    cosigner_weights: &[u8],
+    num_cosigners: usize,
+) -> Result<(), AppError> {
+    if cosigner_weights.len() != num_cosigners {
+        return Err(AppError::InvalidCosignerWeight(s!(
+            "there must be one weight for each cosigner xPub"
+        )));
+    }
+    if cosigner_weights.contains(&0) {
+        return Err(AppError::InvalidCosignerWeight(s!(
+            "weights must be positive values"
+        )));
+    }
+    // weights and thresholds are stored as u8 values
+    if cosigner_weights.iter().map(|w| *w as u32).sum::<u32>() > u8::MAX as u32 {
+        return Err(AppError::InvalidCosignerWeight(format!(
+            "total weight cannot be higher than {}",
+            u8::MAX
+        )));
+    }
+    Ok(())
+}
+
+fn validate_cosigner_xpubs(
+    cosigner_xpubs: &[String],
+    network: BitcoinNetwork,
+) -> Result<(), AppError> {
+    let mut keys = HashSet::new();
+    for xpub in cosigner_xpubs {
+        let key = parse_xpub(xpub).ok_or(AppError::InvalidCosignerXpub(format!(
+            "'{xpub}' is not a valid extended public key"
+        )))?;
+        if key.network != network.network_kind() {
+            return Err(AppError::InvalidCosignerXpub(format!(
+                "'{xpub}' does not belong to the {network:?} network"
+            )));
+        }
+        if !keys.insert(key) {
+            return Err(AppError::InvalidCosignerXpub(format!(
+                "'{xpub}' is a duplicate"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_thresholds(
+    thresholds: &HashMap<OperationType, u8>,
+    total_weight: u32,
+    auto_approved_ops: &[OperationType],
+) -> Result<(), AppError> {
+    for (op_type, threshold) in thresholds {
+        if auto_approved_ops.contains(op_type) {
+            return Err(AppError::InvalidThreshold(format!(
+                "cannot set a threshold for auto-approved operation type {op_type:?}"
+            )));
+        }
+        if *threshold == 0 || *threshold as u32 > total_weight {
+            return Err(AppError::InvalidThreshold(format!(
+                "threshold for {op_type:?} must be between 1 and the total cosigner weight"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_webhooks(webhooks: &[WebhookConfig]) -> Result<(), AppError> {
+    let mut urls = HashSet::new();
+    for webhook in webhooks {
+        let url = reqwest::Url::parse(&webhook.url)
+            .map_err(|e| AppError::InvalidWebhook(format!("invalid URL '{}': {e}", webhook.url)))?;
+        if !["http", "https"].contains(&url.scheme()) {
+            return Err(AppError::InvalidWebhook(format!(
+                "unsupported URL scheme for '{}'",
+                webhook.url
+            )));
+        }
+        if webhook.secret.is_empty() {
+            return Err(AppError::InvalidWebhook(format!(
+                "missing secret for '{}'",
+                webhook.url
+            )));
+        }
+        if !urls.insert(&webhook.url) {
+            return Err(AppError::InvalidWebhook(format!(
+                "duplicate URL '{}'",
+                webhook.url
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_policies(
+    policies: &[PolicyConfig],
+    total_weight: u32,
+    auto_approved_ops: &[OperationType],
+) -> Result<(), AppError> {
+    for (idx, policy) in policies.iter().enumerate() {
+        let policy_num = idx + 1;
+        if policy.threshold.is_some() == policy.reject {
+            return Err(AppError::InvalidPolicy(format!(
+                "policy #{policy_num} must either set a threshold or reject"
+            )));
+        }
+        if let Some(threshold) = policy.threshold
+            && (threshold == 0 || threshold as u32 > total_weight)
+        {
+            return Err(AppError::InvalidPolicy(format!(
+                "policy #{policy_num} threshold must be between 1 and the total cosigner weight"
+            )));
+        }
+        if let Some(fee_rate) = policy.fee_rate_above
+            && (fee_rate.is_nan() || fee_rate < 0.0)
+        {
+            return Err(AppError::InvalidPolicy(format!(
+                "policy #{policy_num} fee rate must be a non-negative number"
+            )));
+        }
+        if let Some(op_type) = policy
+            .operation_types
+            .iter()
+            .find(|t| auto_approved_ops.contains(t))
+        {
+            return Err(AppError::InvalidPolicy(format!(
+                "policy #{policy_num} cannot apply to auto-approved operation type {op_type:?}"
+            )));
+        }
+        if policy
+            .operation_types
+            .contains(&OperationType::UpdateAllowlist)
+        {
+            return Err(AppError::InvalidPolicy(format!(
+                "policy #{policy_num} cannot apply to allowlist updates, which have no PSBT"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_spending_limits(
+    spending_limits: &[SpendingLimitConfig],
+    total_weight: u32,
+    auto_approved_ops: &[OperationType],
+) -> Result<(), AppError> {
+    // limits are checked only on operations requiring approval, so operation types that can
+    // move funds cannot bypass them by being auto-approved
+    if !spending_limits.is_empty()
+        && let Some(op_type) = auto_approved_ops
+            .iter()
+            .find(|t| !DEFAULT_AUTO_APPROVED_OPS.contains(t))
+    {
+        return Err(AppError::InvalidSpendingLimit(format!(
+            "cannot be enforced with auto-approved operation type {op_type:?}"
+        )));
+    }
+    for (idx, limit) in spending_limits.iter().enumerate() {
+        let limit_num = idx + 1;
+        if limit.threshold.is_some() == limit.reject {
+            return Err(AppError::InvalidSpendingLimit(format!(
+                "spending limit #{limit_num} must either set a threshold or reject"
+            )));
+        }
+        if let Some(threshold) = limit.threshold
+            && (threshold == 0 || threshold as u32 > total_weight)
+        {
+            return Err(AppError::InvalidSpendingLimit(format!(
+                "spending limit #{limit_num} threshold must be between 1 and the total cosigner weight"
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn validate_rgb_lib_version(
     version: &str,
     min_version: &str,
@@ -215,17 +653,62 @@ pub(crate) async fn start_daemon(app_params: &AppParams) -> Result<Arc<AppState>
 
     let db_cosigners = if let Some(db_config) = database.get_config().await? {
         // already started at least once
+        let db_thresholds: HashMap<OperationType, u8> = serde_json::from_str(&db_config.thresholds)
+            .map_err(|e| AppError::InconsistentState(format!("invalid stored thresholds: {e}")))?;
+        let db_auto_approved_ops: HashSet<OperationType> =
+            serde_json::from_str(&db_config.auto_approved_ops).map_err(|e| {
+                AppError::InconsistentState(format!("invalid stored auto-approved types: {e}"))
+            })?;
         if db_config.threshold_colored != app_params.threshold_colored
             || db_config.threshold_vanilla != app_params.threshold_vanilla
+            || db_thresholds != app_params.thresholds
         {
             return Err(AppError::InvalidThreshold(s!(
                 "cannot change threshold on already configured service"
             )));
         }
+        let cfg_auto_approved_ops: HashSet<OperationType> =
+            app_params.auto_approved_ops.iter().copied().collect();
+        if db_auto_approved_ops != cfg_auto_approved_ops {
+            return Err(AppError::InvalidAutoApprovedOps(s!(
+                "cannot change auto-approved operation types on already configured service"
+            )));
+        }
+        match db_config.network {
+            Some(network) if network != app_params.network => {
+                return Err(AppError::CannotChangeNetwork);
+            }
+            Some(_) => {}
+            None => {
+                // configured before the network was persisted
+                let mut config: config::ActiveModel = db_config.into();
+                config.network = ActiveValue::Set(Some(app_params.network));
+                database.update_config(config).await?;
+            }
+        }
         let db_cosigners = database.iter_cosigners::<AppError>().await?;
         let db_xpubs: HashSet<&String> = db_cosigners.iter().map(|c| &c.xpub).collect();
         let cfg_xpubs: HashSet<&String> = app_params.cosigner_xpubs.iter().collect();
-        if db_xpubs != cfg_xpubs {
+        let db_weights: HashMap<&String, u8> =
+            db_cosigners.iter().map(|c| (&c.xpub, c.weight)).collect();
+        let cfg_weights: HashMap<&String, u8> = app_params
+            .cosigner_xpubs
+            .iter()
+            .zip(app_params.cosigner_weights.iter().copied())
+            .collect();
+        let db_veto_holders: HashSet<&String> = db_cosigners
+            .iter()
+            .filter(|c| c.veto)
+            .map(|c| &c.xpub)
+            .collect();
+        let cfg_veto_holders: HashSet<&String> = app_params
+            .cosigner_roles
+            .iter()
+            .filter(|(_, roles)| roles.veto)
+            .map(|(xpub, _)| xpub)
+            .collect();
+        if db_xpubs != cfg_xpubs || db_weights != cfg_weights || db_veto_holders != cfg_veto_holders
+        {
             return Err(AppError::CannotChangeCosigners);
         }
         db_cosigners
@@ -235,6 +718,14 @@ pub(crate) async fn start_daemon(app_params: &AppParams) -> Result<Arc<AppState>
         let config = config::ActiveModel {
             threshold_colored: ActiveValue::Set(app_params.threshold_colored),
             threshold_vanilla: ActiveValue::Set(app_params.threshold_vanilla),
+            network: ActiveValue::Set(Some(app_params.network)),
+            thresholds: ActiveValue::Set(
+                serde_json::to_string(&app_params.thresholds).expect("thresholds serialize"),
+            ),
+            auto_approved_ops: ActiveValue::Set(
+                serde_json::to_string(&app_params.auto_approved_ops)
+                    .expect("auto-approved types serialize"),
+            ),
             ..Default::default()
         };
         let idx = database.set_config(config).await?;
@@ -247,8 +738,16 @@ pub(crate) async fn start_daemon(app_params: &AppParams) -> Result<Arc<AppState>
         let cosigners = app_params
             .cosigner_xpubs
             .iter()
-            .map(|xpub| cosigner::ActiveModel {
+            .zip(&app_params.cosigner_weights)
+            .map(|(xpub, weight)| cosigner::ActiveModel {
                 xpub: ActiveValue::Set(xpub.clone()),
+                weight: ActiveValue::Set(*weight),
+                veto: ActiveValue::Set(
+                    app_params
+                        .cosigner_roles
+                        .get(xpub)
+                        .is_some_and(|roles| roles.veto),
+                ),
                 ..Default::default()
             })
             .collect();
@@ -277,22 +776,73 @@ pub(crate) async fn start_daemon(app_params: &AppParams) -> Result<Arc<AppState>
         .iter()
         .map(|c| (c.xpub.clone(), c.idx))
         .collect();
+    let cosigner_keys_by_idx = db_cosigners
+        .iter()
+        .map(|c| (c.idx, parse_xpub(&c.xpub).expect("validated on startup")))
+        .collect();
+    let cosigner_weights_by_idx = db_cosigners.iter().map(|c| (c.idx, c.weight)).collect();
+    let veto_holders = db_cosigners
+        .iter()
+        .filter(|c| c.veto)
+        .map(|c| c.idx)
+        .collect();
     let cosigners_by_idx = db_cosigners.into_iter().map(|c| (c.idx, c.xpub)).collect();
 
     let cancel_token = CancellationToken::new();
+    let (event_sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
 
-    Ok(Arc::new(AppState {
+    let app_state = Arc::new(AppState {
         files_dir,
         database,
         cancel_token,
         root_public_key: app_params.root_public_key,
+        network: app_params.network,
         cosigners_by_xpub,
         cosigners_by_idx,
+        cosigner_keys_by_idx,
+        cosigner_weights_by_idx,
+        veto_holders,
         threshold_colored: app_params.threshold_colored,
         threshold_vanilla: app_params.threshold_vanilla,
+        thresholds: app_params.thresholds.clone(),
+        auto_approved_ops: app_params.auto_approved_ops.clone(),
         rgb_lib_version: app_params.rgb_lib_version.clone(),
+        pending_timeout: app_params.pending_timeout,
+        event_sender,
+        operation_notify: Notify::new(),
+        webhooks: app_params.webhooks.clone(),
+        webhook_notify: Notify::new(),
+        enforce_address_allowlist: app_params.enforce_address_allowlist,
+        policies: app_params.policies.clone(),
+        spending_limits: app_params.spending_limits.clone(),
+        approval_delays: app_params.approval_delays.clone(),
         write_lock: Arc::new(Mutex::new(())),
-    }))
+    });
+
+    if let Some(pending_timeout) = app_params.pending_timeout {
+        tokio::spawn(expire_pending_operations(
+            app_state.clone(),
+            pending_timeout,
+        ));
+    }
+
+    if let Some(min_delay) = app_params.approval_delays.values().min() {
+        tokio::spawn(approve_delayed_operations(app_state.clone(), *min_delay));
+    }
+
+    if !app_state.webhooks.is_empty() {
+        tokio::spawn(deliver_webhooks(app_state.clone()));
+    }
+
+    if let Some(chain_source) = &app_params.chain_source {
+        tokio::spawn(track_confirmations(
+            app_state.clone(),
+            EsploraChainSource::new(&chain_source.esplora_url),
+            Duration::from_secs(chain_source.poll_interval),
+        ));
+    }
+
+    Ok(app_state)
 }
 
 #[cfg(test)]
@@ -300,6 +850,10 @@ mod tests {
     use super::*;
     use std::net::TcpListener;
 
+    const XPUB_1: &str = "tpubD6NzVbkrYhZ4XJ6aDsDYTCUkn1QqC6ie7eappEWB823FLSsRo1VBoEmtQVPJEJYdBt1UArW74BJg54FbW217Xoae6SDgj71JQZTfYCSJUyy";
+    const XPUB_2: &str = "tpubD6NzVbkrYhZ4XoJ4SGokACCMyKUYycuuu4tNDAW9qQrksXPNU9C9jeqQJQsdd18Dgt5v2hcc1w4qjNqYQg4nJ15YQNBHsWUuv2cEmneU7Mn";
+    const MAINNET_XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
     #[test]
     fn test_parse_version() {
         // valid versions
@@ -364,19 +918,35 @@ mod tests {
             daemon_listening_port: 3333,
         };
         let config = AppConfig {
-            cosigner_xpubs: vec![s!("xpub1"), s!("xpub2")],
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
             threshold_colored: 2,
             threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
             root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
             rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
         };
         let params = parse_args_and_config_internal(args, config).unwrap();
         assert_eq!(params.app_dir, PathBuf::from("test"));
         assert_eq!(params.daemon_listening_port, 3333);
-        assert_eq!(params.cosigner_xpubs, vec![s!("xpub1"), s!("xpub2")]);
+        assert_eq!(
+            params.cosigner_xpubs,
+            vec![XPUB_1.to_string(), XPUB_2.to_string()]
+        );
         assert_eq!(params.threshold_colored, 2);
         assert_eq!(params.threshold_vanilla, 2);
         assert_eq!(params.rgb_lib_version, s!("0.3"));
+        assert_eq!(params.pending_timeout, None);
 
         // insufficient cosigners
         let args = AppArgs {
@@ -384,11 +954,23 @@ mod tests {
             daemon_listening_port: 3333,
         };
         let config = AppConfig {
-            cosigner_xpubs: vec![s!("xpub1")],
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
             threshold_colored: 1,
             threshold_vanilla: 1,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
             root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
             rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
         };
         let result = parse_args_and_config_internal(args, config);
         assert!(matches!(
@@ -396,17 +978,176 @@ mod tests {
             AppError::InvalidCosignerNumber(1)
         ));
 
+        // invalid cosigner xPubs
+        let invalid_xpubs = [
+            (
+                vec![XPUB_1.to_string(), s!("xpub2")],
+                "is not a valid extended public key",
+            ),
+            (
+                vec![XPUB_1.to_string(), XPUB_1.to_string()],
+                "is a duplicate",
+            ),
+            (
+                vec![XPUB_1.to_string(), MAINNET_XPUB.to_string()],
+                "does not belong to the Regtest network",
+            ),
+        ];
+        for (cosigner_xpubs, expected) in invalid_xpubs {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs,
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidCosignerXpub(e) if e.contains(expected)
+            ));
+        }
+
+        // invalid cosigner weights
+        let invalid_weights = [
+            (vec![1], "there must be one weight for each cosigner xPub"),
+            (vec![1, 0], "weights must be positive values"),
+            (vec![200, 56], "total weight cannot be higher than 255"),
+        ];
+        for (cosigner_weights, expected) in invalid_weights {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights,
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 1,
+                threshold_vanilla: 1,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidCosignerWeight(e) if e == expected
+            ));
+        }
+
+        // threshold within the total cosigner weight
+        let args = AppArgs {
+            app_directory_path: PathBuf::from("test"),
+            daemon_listening_port: 3333,
+        };
+        let config = AppConfig {
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![2, 1],
+            cosigner_roles: HashMap::new(),
+            threshold_colored: 3,
+            threshold_vanilla: 3,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+            root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
+            rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
+        };
+        let params = parse_args_and_config_internal(args, config).unwrap();
+        assert_eq!(params.cosigner_weights, vec![2, 1]);
+
+        // role for an xPub that is not a cosigner
+        let args = AppArgs {
+            app_directory_path: PathBuf::from("test"),
+            daemon_listening_port: 3333,
+        };
+        let config = AppConfig {
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::from([(
+                MAINNET_XPUB.to_string(),
+                CosignerRolesConfig { veto: true },
+            )]),
+            threshold_colored: 2,
+            threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+            root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
+            rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
+        };
+        let result = parse_args_and_config_internal(args, config);
+        assert!(matches!(
+            result.unwrap_err(),
+            AppError::InvalidCosignerRole(e) if e.contains("is not a cosigner xPub")
+        ));
+
         // zero threshold
         let args = AppArgs {
             app_directory_path: PathBuf::from("test"),
             daemon_listening_port: 3333,
         };
         let config = AppConfig {
-            cosigner_xpubs: vec![s!("xpub1"), s!("xpub2")],
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
             threshold_colored: 0,
             threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
             root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
             rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
         };
         let result = parse_args_and_config_internal(args, config);
         assert!(matches!(
@@ -420,29 +1161,570 @@ mod tests {
             daemon_listening_port: 3333,
         };
         let config = AppConfig {
-            cosigner_xpubs: vec![s!("xpub1"), s!("xpub2")],
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
             threshold_colored: 3,
             threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
             root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
             rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
         };
         let result = parse_args_and_config_internal(args, config);
         assert!(matches!(
             result.unwrap_err(),
-            AppError::InvalidThreshold(e) if e == "cannot be higher than number of cosigners"
+            AppError::InvalidThreshold(e) if e == "cannot be higher than the total cosigner weight"
         ));
 
+        // zero or too long pending timeout
+        for pending_timeout in [0, MAX_PENDING_TIMEOUT + 1] {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: Some(pending_timeout),
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidPendingTimeout
+            ));
+        }
+
+        // invalid webhooks
+        let invalid_webhooks = [
+            (s!("not a url"), s!("secret"), "invalid URL"),
+            (
+                s!("ftp://example.com/hook"),
+                s!("secret"),
+                "unsupported URL scheme",
+            ),
+            (s!("https://example.com/hook"), s!(""), "missing secret"),
+        ];
+        for (url, secret, expected) in invalid_webhooks {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![WebhookConfig { url, secret }],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidWebhook(e) if e.contains(expected)
+            ));
+        }
+        let args = AppArgs {
+            app_directory_path: PathBuf::from("test"),
+            daemon_listening_port: 3333,
+        };
+        let webhook = WebhookConfig {
+            url: s!("https://example.com/hook"),
+            secret: s!("secret"),
+        };
+        let config = AppConfig {
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
+            threshold_colored: 2,
+            threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+            root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
+            rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![webhook.clone(), webhook],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
+        };
+        let result = parse_args_and_config_internal(args, config);
+        assert!(matches!(
+            result.unwrap_err(),
+            AppError::InvalidWebhook(e) if e.contains("duplicate URL")
+        ));
+
+        // invalid policies
+        let invalid_policies = [
+            (
+                PolicyConfig::default(),
+                "must either set a threshold or reject",
+            ),
+            (
+                PolicyConfig {
+                    threshold: Some(2),
+                    reject: true,
+                    ..Default::default()
+                },
+                "must either set a threshold or reject",
+            ),
+            (
+                PolicyConfig {
+                    threshold: Some(3),
+                    ..Default::default()
+                },
+                "threshold must be between 1 and the total cosigner weight",
+            ),
+            (
+                PolicyConfig {
+                    fee_rate_above: Some(-1.0),
+                    reject: true,
+                    ..Default::default()
+                },
+                "fee rate must be a non-negative number",
+            ),
+            (
+                PolicyConfig {
+                    operation_types: vec![OperationType::SendBtc, OperationType::Issuance],
+                    reject: true,
+                    ..Default::default()
+                },
+                "cannot apply to auto-approved operation type Issuance",
+            ),
+            (
+                PolicyConfig {
+                    operation_types: vec![OperationType::UpdateAllowlist],
+                    reject: true,
+                    ..Default::default()
+                },
+                "cannot apply to allowlist updates",
+            ),
+        ];
+        for (policy, expected) in invalid_policies {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![policy],
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidPolicy(e) if e.contains("policy #1") && e.contains(expected)
+            ));
+        }
+
+        // invalid spending limits
+        let invalid_spending_limits = [
+            (
+                SpendingLimitConfig {
+                    amount_sat: 1000,
+                    ..Default::default()
+                },
+                "must either set a threshold or reject",
+            ),
+            (
+                SpendingLimitConfig {
+                    window: SpendingLimitWindow::Weekly,
+                    amount_sat: 1000,
+                    threshold: Some(0),
+                    ..Default::default()
+                },
+                "threshold must be between 1 and the total cosigner weight",
+            ),
+        ];
+        for (spending_limit, expected) in invalid_spending_limits {
+            let expected = format!("spending limit #1 {expected}");
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![spending_limit],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidSpendingLimit(e) if e.contains(&expected)
+            ));
+        }
+
+        // spending limits with auto-approved operation types moving funds
+        let args = AppArgs {
+            app_directory_path: PathBuf::from("test"),
+            daemon_listening_port: 3333,
+        };
+        let config = AppConfig {
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
+            threshold_colored: 2,
+            threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: vec![OperationType::Issuance, OperationType::SendBtc],
+            root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
+            rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![SpendingLimitConfig {
+                amount_sat: 1000,
+                reject: true,
+                ..Default::default()
+            }],
+            approval_delays: HashMap::new(),
+            chain_source: None,
+        };
+        let result = parse_args_and_config_internal(args, config);
+        assert!(matches!(
+            result.unwrap_err(),
+            AppError::InvalidSpendingLimit(e)
+                if e == "cannot be enforced with auto-approved operation type SendBtc"
+        ));
+
+        // invalid auto-approved operation types
+        let policy = PolicyConfig {
+            reject: true,
+            operation_types: vec![OperationType::SendRgb],
+            ..Default::default()
+        };
+        let invalid_auto_approved_ops = [
+            (
+                vec![OperationType::UpdateAllowlist],
+                false,
+                vec![],
+                "allowlist updates always require approval",
+            ),
+            (
+                vec![OperationType::Issuance, OperationType::Issuance],
+                false,
+                vec![],
+                "Issuance is a duplicate",
+            ),
+            (
+                vec![OperationType::Issuance, OperationType::CreateUtxos],
+                false,
+                vec![policy],
+                "CreateUtxos cannot be auto-approved when policies are set",
+            ),
+            (
+                vec![OperationType::SendBtc],
+                true,
+                vec![],
+                "SendBtc cannot be auto-approved when the address allowlist is enforced",
+            ),
+        ];
+        for (auto_approved_ops, enforce_address_allowlist, policies, expected) in
+            invalid_auto_approved_ops
+        {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops,
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist,
+                policies,
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidAutoApprovedOps(e) if e == expected
+            ));
+        }
+
+        // threshold for an operation type that is no longer auto-approved
+        let args = AppArgs {
+            app_directory_path: PathBuf::from("test"),
+            daemon_listening_port: 3333,
+        };
+        let config = AppConfig {
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
+            threshold_colored: 2,
+            threshold_vanilla: 2,
+            thresholds: HashMap::from([(OperationType::Issuance, 1)]),
+            auto_approved_ops: vec![],
+            root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
+            rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
+        };
+        let params = parse_args_and_config_internal(args, config).unwrap();
+        assert!(params.auto_approved_ops.is_empty());
+
+        // invalid operation type thresholds
+        let invalid_thresholds = [
+            (
+                OperationType::SendBtc,
+                0,
+                "threshold for SendBtc must be between 1 and the total cosigner weight",
+            ),
+            (
+                OperationType::SendRgb,
+                3,
+                "threshold for SendRgb must be between 1 and the total cosigner weight",
+            ),
+            (
+                OperationType::BlindReceive,
+                1,
+                "cannot set a threshold for auto-approved operation type BlindReceive",
+            ),
+        ];
+        for (op_type, threshold, expected) in invalid_thresholds {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::from([(op_type, threshold)]),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidThreshold(e) if e == expected
+            ));
+        }
+
+        // invalid approval delays
+        let invalid_approval_delays = [
+            (
+                OperationType::SendBtc,
+                0,
+                "delay for SendBtc must be a positive number of seconds",
+            ),
+            (
+                OperationType::Issuance,
+                60,
+                "cannot delay auto-approved operation type Issuance",
+            ),
+        ];
+        for (op_type, delay, expected) in invalid_approval_delays {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![],
+                approval_delays: HashMap::from([(op_type, delay)]),
+                chain_source: None,
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidApprovalDelay(e) if e == expected
+            ));
+        }
+
+        // invalid chain source
+        let invalid_chain_sources = [
+            ("not a url", 60, "invalid URL 'not a url'"),
+            (
+                "ftp://esplora.example.com",
+                60,
+                "unsupported URL scheme for 'ftp://esplora.example.com'",
+            ),
+            (
+                "https://esplora.example.com",
+                0,
+                "poll interval must be a positive number of seconds",
+            ),
+        ];
+        for (esplora_url, poll_interval, expected) in invalid_chain_sources {
+            let args = AppArgs {
+                app_directory_path: PathBuf::from("test"),
+                daemon_listening_port: 3333,
+            };
+            let config = AppConfig {
+                network: BitcoinNetwork::Regtest,
+                cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+                cosigner_weights: vec![],
+                cosigner_roles: HashMap::new(),
+                threshold_colored: 2,
+                threshold_vanilla: 2,
+                thresholds: HashMap::new(),
+                auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+                root_public_key: s!(
+                    "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
+                ),
+                rgb_lib_version: s!("0.3"),
+                pending_timeout: None,
+                webhooks: vec![],
+                enforce_address_allowlist: false,
+                policies: vec![],
+                spending_limits: vec![],
+                approval_delays: HashMap::new(),
+                chain_source: Some(ChainSourceConfig {
+                    esplora_url: esplora_url.to_string(),
+                    poll_interval,
+                }),
+            };
+            let result = parse_args_and_config_internal(args, config);
+            assert!(matches!(
+                result.unwrap_err(),
+                AppError::InvalidChainSource(e) if e.contains(expected)
+            ));
+        }
+
         // invalid public key
         let args = AppArgs {
             app_directory_path: PathBuf::from("test"),
             daemon_listening_port: 3333,
         };
         let config = AppConfig {
-            cosigner_xpubs: vec![s!("xpub1"), s!("xpub2")],
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
             threshold_colored: 2,
             threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
             root_public_key: s!("invalid_key"),
             rgb_lib_version: s!("0.3"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
         };
         let result = parse_args_and_config_internal(args, config);
         assert!(matches!(result.unwrap_err(), AppError::InvalidRootKey));
@@ -453,11 +1735,23 @@ mod tests {
             daemon_listening_port: 3333,
         };
         let config = AppConfig {
-            cosigner_xpubs: vec![s!("xpub1"), s!("xpub2")],
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
             threshold_colored: 2,
             threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
             root_public_key: s!("0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"),
             rgb_lib_version: s!("0.2"),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
         };
         let result = parse_args_and_config_internal(args, config);
         assert!(result.is_err());
@@ -480,12 +1774,24 @@ mod tests {
             daemon_listening_port: port,
         };
         let config = AppConfig {
-            cosigner_xpubs: vec!["xpub1".to_string(), "xpub2".to_string()],
+            network: BitcoinNetwork::Regtest,
+            cosigner_xpubs: vec![XPUB_1.to_string(), XPUB_2.to_string()],
+            cosigner_weights: vec![],
+            cosigner_roles: HashMap::new(),
             threshold_colored: 2,
             threshold_vanilla: 2,
+            thresholds: HashMap::new(),
+            auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
             root_public_key: "0606bc5f1e32cb636c96911fc3e97174609d51ee5304a319610f451e8b1112ca"
                 .to_string(),
             rgb_lib_version: "0.3".to_string(),
+            pending_timeout: None,
+            webhooks: vec![],
+            enforce_address_allowlist: false,
+            policies: vec![],
+            spending_limits: vec![],
+            approval_delays: HashMap::new(),
+            chain_source: None,
         };
         let result = parse_args_and_config_internal(args, config);
         assert!(matches!(result.unwrap_err(), AppError::UnavailablePort(p) if p == port));
@@ -0,0 +1,303 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use amplify::s;
+use bitcoin::Txid;
+use sea_orm::ActiveValue;
+
+use crate::{
+    chain::ChainSource,
+    database::entities::{operation, webhook_delivery},
+    error::APIError,
+    routes::{ChainStatus, EventType, OperationStatus, WebhookDeliveryStatus},
+    startup::{AppState, WebhookConfig},
+    utils::{now, sign_webhook_payload},
+};
+
+// maximum interval between checks for an expired pending operation
+const MAX_EXPIRY_CHECK_INTERVAL_SECS: u64 = 60;
+// maximum interval between checks for an operation whose approval delay has ended
+const MAX_DELAY_CHECK_INTERVAL_SECS: u64 = 60;
+
+// interval between checks for due webhook deliveries, when not notified of new ones
+const WEBHOOK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+// timeout for a single webhook delivery attempt
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// delivery attempts before a webhook delivery is marked as failed
+const WEBHOOK_MAX_ATTEMPTS: u32 = 10;
+// retry delay after the first failed attempt, doubled after each following one
+const WEBHOOK_BASE_RETRY_DELAY_SECS: i64 = 2;
+const WEBHOOK_MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+pub(crate) const WEBHOOK_SIGNATURE_HEADER: &str = "x-signature-256";
+
+pub(crate) async fn expire_pending_operations(state: Arc<AppState>, pending_timeout: u64) {
+    let interval = Duration::from_secs(pending_timeout.min(MAX_EXPIRY_CHECK_INTERVAL_SECS));
+    loop {
+        tokio::select! {
+            _ = state.cancel_token.cancelled() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+        if let Err(e) = expire_pending_operation(&state).await {
+            tracing::error!("Failed to expire pending operation: {e}");
+        }
+    }
+}
+
+async fn expire_pending_operation(state: &AppState) -> Result<(), APIError> {
+    // acquire write lock to prevent concurrent write operations
+    let _lock = state.write_lock.lock().await;
+
+    // check if the pending operation, if any, is past its deadline
+    let Some(op) = state.database.get_pending_operation().await? else {
+        return Ok(());
+    };
+    if !state.is_operation_expired(&op) {
+        return Ok(());
+    }
+
+    // update operation status
+    let operation_idx = op.idx;
+    let txn = state.database.begin_transaction().await?;
+    let mut operation: operation::ActiveModel = op.into();
+    operation.status = ActiveValue::Set(OperationStatus::Expired);
+    state.database.update_operation(operation, &txn).await?;
+    let event = state
+        .record_event(EventType::OperationExpired, operation_idx, None, &txn)
+        .await?;
+    txn.commit().await?;
+    state.publish_events(vec![event]);
+    tracing::info!("Operation {operation_idx} expired");
+
+    Ok(())
+}
+
+pub(crate) async fn approve_delayed_operations(state: Arc<AppState>, min_delay: u64) {
+    let interval = Duration::from_secs(min_delay.min(MAX_DELAY_CHECK_INTERVAL_SECS));
+    loop {
+        tokio::select! {
+            _ = state.cancel_token.cancelled() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+        if let Err(e) = approve_delayed_operation(&state).await {
+            tracing::error!("Failed to approve delayed operation: {e}");
+        }
+    }
+}
+
+async fn approve_delayed_operation(state: &AppState) -> Result<(), APIError> {
+    // acquire write lock to prevent concurrent write operations
+    let _lock = state.write_lock.lock().await;
+
+    // check if the delayed operation, if any, is past its approval delay
+    let Some(op) = state.database.get_delayed_operation().await? else {
+        return Ok(());
+    };
+    if op
+        .delay_ends_at
+        .is_some_and(|delay_ends_at| now().unix_timestamp() < delay_ends_at)
+    {
+        return Ok(());
+    }
+
+    // approve the operation
+    let operation_idx = op.idx;
+    let txn = state.database.begin_transaction().await?;
+    match state.approve_operation(op.clone(), None, &txn).await {
+        Ok(event) => {
+            txn.commit().await?;
+            state.publish_events(vec![event]);
+            tracing::info!("Operation {operation_idx} approved after its delay");
+        }
+        Err(APIError::InvalidPsbt(e)) => {
+            // the ACK PSBTs can never be combined, so discard the operation instead of retrying
+            txn.rollback().await?;
+            let txn = state.database.begin_transaction().await?;
+            let mut operation: operation::ActiveModel = op.into();
+            operation.status = ActiveValue::Set(OperationStatus::Discarded);
+            state.database.update_operation(operation, &txn).await?;
+            let event = state
+                .record_event(EventType::OperationDiscarded, operation_idx, None, &txn)
+                .await?;
+            txn.commit().await?;
+            state.publish_events(vec![event]);
+            tracing::error!("Operation {operation_idx} discarded after its delay: {e}");
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn deliver_webhooks(state: Arc<AppState>) {
+    let client = reqwest::Client::builder()
+        .timeout(WEBHOOK_REQUEST_TIMEOUT)
+        .build()
+        .expect("valid HTTP client configuration");
+    loop {
+        if let Err(e) = deliver_due_webhooks(&state, &client).await {
+            tracing::error!("Failed to deliver webhooks: {e}");
+        }
+        tokio::select! {
+            _ = state.cancel_token.cancelled() => break,
+            _ = state.webhook_notify.notified() => {}
+            _ = tokio::time::sleep(WEBHOOK_CHECK_INTERVAL) => {}
+        }
+    }
+}
+
+async fn deliver_due_webhooks(state: &AppState, client: &reqwest::Client) -> Result<(), APIError> {
+    let deliveries = state
+        .database
+        .iter_due_webhook_deliveries(now().unix_timestamp())
+        .await?;
+    for delivery in deliveries {
+        if state.cancel_token.is_cancelled() {
+            break;
+        }
+        let delivery_idx = delivery.idx;
+
+        // deliveries to webhooks removed from the configuration cannot be completed
+        let Some(webhook) = state.webhooks.iter().find(|w| w.url == delivery.url) else {
+            let mut delivery: webhook_delivery::ActiveModel = delivery.into();
+            delivery.status = ActiveValue::Set(WebhookDeliveryStatus::Failed);
+            delivery.last_error = ActiveValue::Set(Some(s!("webhook is no longer configured")));
+            state.database.update_webhook_delivery(delivery).await?;
+            continue;
+        };
+
+        // the payload is built along with the event, deliveries queued without one cannot be
+        // completed
+        let Some(payload) = delivery.payload.clone() else {
+            let mut delivery: webhook_delivery::ActiveModel = delivery.into();
+            delivery.status = ActiveValue::Set(WebhookDeliveryStatus::Failed);
+            delivery.last_error = ActiveValue::Set(Some(s!("webhook payload is missing")));
+            state.database.update_webhook_delivery(delivery).await?;
+            continue;
+        };
+
+        // attempt delivery and record its outcome
+        let result = send_webhook(client, webhook, payload).await;
+        let attempts = delivery.attempts + 1;
+        let now = now().unix_timestamp();
+        let mut delivery: webhook_delivery::ActiveModel = delivery.into();
+        delivery.attempts = ActiveValue::Set(attempts);
+        match result {
+            Ok(()) => {
+                delivery.status = ActiveValue::Set(WebhookDeliveryStatus::Delivered);
+                delivery.delivered_at = ActiveValue::Set(Some(now));
+                delivery.last_error = ActiveValue::Set(None);
+                tracing::info!(
+                    "Webhook delivery {delivery_idx} delivered to {}",
+                    webhook.url
+                );
+            }
+            Err(e) => {
+                if attempts >= WEBHOOK_MAX_ATTEMPTS {
+                    delivery.status = ActiveValue::Set(WebhookDeliveryStatus::Failed);
+                    tracing::error!("Webhook delivery {delivery_idx} failed: {e}");
+                } else {
+                    let retry_delay = WEBHOOK_BASE_RETRY_DELAY_SECS
+                        .saturating_mul(1 << (attempts - 1))
+                        .min(WEBHOOK_MAX_RETRY_DELAY_SECS);
+                    delivery.next_attempt_at = ActiveValue::Set(now + retry_delay);
+                    tracing::warn!(
+                        "Webhook delivery {delivery_idx} attempt {attempts} failed, retrying in {retry_delay}s: {e}"
+                    );
+                }
+                delivery.last_error = ActiveValue::Set(Some(e));
+            }
+        }
+        state.database.update_webhook_delivery(delivery).await?;
+    }
+    Ok(())
+}
+
+async fn send_webhook(
+    client: &reqwest::Client,
+    webhook: &WebhookConfig,
+    payload: String,
+) -> Result<(), String> {
+    let signature = sign_webhook_payload(&webhook.secret, payload.as_bytes());
+    let res = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_SIGNATURE_HEADER, format!("sha256={signature}"))
+        .body(payload)
+        .send()
+        .await
+        .map_err(|e| format!("request failed: {e}"))?;
+    if !res.status().is_success() {
+        return Err(format!("unexpected response status {}", res.status()));
+    }
+    Ok(())
+}
+
+pub(crate) async fn track_confirmations<C: ChainSource>(
+    state: Arc<AppState>,
+    chain_source: C,
+    interval: Duration,
+) {
+    loop {
+        if let Err(e) = check_confirmations(&state, &chain_source).await {
+            tracing::error!("Failed to check confirmations: {e}");
+        }
+        tokio::select! {
+            _ = state.cancel_token.cancelled() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+}
+
+async fn check_confirmations<C: ChainSource>(
+    state: &AppState,
+    chain_source: &C,
+) -> Result<(), APIError> {
+    for op in state.database.iter_broadcast_operations().await? {
+        if state.cancel_token.is_cancelled() {
+            break;
+        }
+        let txid = op
+            .txid
+            .as_deref()
+            .expect("broadcast operations have a txid");
+        let txid = Txid::from_str(txid).expect("validated when reported");
+        let block_height = match chain_source.get_tx_block_height(&txid).await {
+            Ok(Some(block_height)) => block_height,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!("Failed to get the status of transaction {txid}: {e}");
+                continue;
+            }
+        };
+
+        // acquire write lock to prevent concurrent write operations
+        let _lock = state.write_lock.lock().await;
+
+        // a cosigner may have updated the chain status in the meantime
+        let Some(op) = state.database.get_operation_by_idx(op.idx, None).await? else {
+            continue;
+        };
+        if op.chain_status != Some(ChainStatus::Broadcast) {
+            continue;
+        }
+
+        // update operation chain status
+        let operation_idx = op.idx;
+        let txid = op.txid.clone();
+        let txn = state.database.begin_transaction().await?;
+        let event = state
+            .update_chain_status(
+                op,
+                ChainStatus::Confirmed,
+                txid,
+                Some(block_height),
+                None,
+                &txn,
+            )
+            .await?;
+        txn.commit().await?;
+        state.publish_events(vec![event]);
+        tracing::info!("Operation {operation_idx} confirmed at height {block_height}");
+    }
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/address_allowlist/";
+
+const PATH: &str = "postoperation";
+
+fn other_address(network: Network) -> String {
+    Address::p2wsh(&ScriptBuf::new(), network).to_string()
+}
+
+async fn post_operation_expecting_error(
+    ctx: &TestContext,
+    form: multipart::Form,
+    expected_status: reqwest::StatusCode,
+    expected_message: &str,
+    expected_name: &str,
+) {
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(res, expected_status, expected_message, expected_name).await;
+}
+
+fn send_form(operation_type: OperationType, with_psbt: bool) -> multipart::Form {
+    let operation_type_part = multipart::Part::bytes((operation_type as u8).to_le_bytes().to_vec());
+    let form = multipart::Form::new().part("operation_type", operation_type_part);
+    if with_psbt {
+        form.part("file_psbt", multipart::Part::bytes(signed_psbt_bytes(0)))
+    } else {
+        form.part("file_consignment", multipart::Part::bytes(vec![1, 2, 3]))
+    }
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.enforce_address_allowlist = true;
+    })
+    .await;
+
+    // allowlist starts empty, so outgoing payments are rejected
+    assert!(info(&ctx, Some(0)).await.address_allowlist.is_empty());
+    post_operation_expecting_error(
+        &ctx,
+        send_form(OperationType::SendBtc, true),
+        reqwest::StatusCode::FORBIDDEN,
+        "Operation rejected by policy: output 0 pays outside the address allowlist",
+        "RejectedByPolicy",
+    )
+    .await;
+
+    // the allowlist update is only applied once the operation is approved
+    let recipient_address = psbt_recipient_address();
+    let form = update_allowlist_form(
+        vec![recipient_address.clone(), other_address(Network::Regtest)],
+        vec![],
+    );
+    let operation_idx = post_operation_with_multipart_form(&ctx, form, 0)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.operation_type, OperationType::UpdateAllowlist);
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert_eq!(res.threshold, Some(3));
+    assert!(res.files.is_empty());
+    let allowlist_update = res.allowlist_update.unwrap();
+    assert_eq!(
+        allowlist_update.add,
+        vec![recipient_address.clone(), other_address(Network::Regtest)]
+    );
+    assert!(allowlist_update.remove.is_empty());
+    let form = respond_to_operation_form(operation_idx, true, None);
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert!(info(&ctx, Some(0)).await.address_allowlist.is_empty());
+    let form = respond_to_operation_form(operation_idx, true, None);
+    let res = respond_to_operation(&ctx, form, 2).await;
+    assert_eq!(res.status, OperationStatus::Approved);
+    assert!(res.files.is_empty());
+    assert_eq!(
+        info(&ctx, None).await.address_allowlist,
+        vec![recipient_address.clone(), other_address(Network::Regtest)]
+    );
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // payments to allowlisted addresses and change are accepted
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    cancel_operation(&ctx, operation_idx, 0).await;
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // a PSBT is required to check outgoing payments
+    post_operation_expecting_error(
+        &ctx,
+        send_form(OperationType::SendRgb, false),
+        reqwest::StatusCode::FORBIDDEN,
+        "Operation rejected by policy: a PSBT is required to check the address allowlist",
+        "RejectedByPolicy",
+    )
+    .await;
+
+    // a discarded allowlist update is not applied
+    let form = update_allowlist_form(vec![], vec![recipient_address.clone()]);
+    let operation_idx = post_operation_with_multipart_form(&ctx, form, 0)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..3 {
+        let form = respond_to_operation_form(operation_idx, false, None);
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Discarded);
+    assert_eq!(info(&ctx, None).await.address_allowlist.len(), 2);
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // removed addresses cannot be paid anymore
+    update_allowlist(&ctx, vec![], vec![recipient_address]).await;
+    assert_eq!(
+        info(&ctx, None).await.address_allowlist,
+        vec![other_address(Network::Regtest)]
+    );
+    post_operation_expecting_error(
+        &ctx,
+        send_form(OperationType::SendRgb, true),
+        reqwest::StatusCode::FORBIDDEN,
+        "Operation rejected by policy: output 0 pays outside the address allowlist",
+        "RejectedByPolicy",
+    )
+    .await;
+
+    // allowlist is not enforced by default
+    let app_dir = format!("{TEST_DIR_BASE}success_not_enforced");
+    let ctx = setup_daemon(&app_dir).await;
+    post_operation(&ctx, OperationType::SendBtc).await;
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let ctx = setup_daemon(&app_dir).await;
+    let recipient_address = psbt_recipient_address();
+    update_allowlist(&ctx, vec![recipient_address.clone()], vec![]).await;
+
+    // allowlist update not provided
+    let operation_type_part = multipart::Part::bytes(
+        (OperationType::UpdateAllowlist as u8)
+            .to_le_bytes()
+            .to_vec(),
+    );
+    let form = multipart::Form::new().part("operation_type", operation_type_part);
+    post_operation_expecting_error(
+        &ctx,
+        form,
+        reqwest::StatusCode::BAD_REQUEST,
+        "allowlist update not provided",
+        "InvalidRequest",
+    )
+    .await;
+
+    // invalid JSON
+    let operation_type_part = multipart::Part::bytes(
+        (OperationType::UpdateAllowlist as u8)
+            .to_le_bytes()
+            .to_vec(),
+    );
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("allowlist_update", multipart::Part::text("{"));
+    post_operation_expecting_error(
+        &ctx,
+        form,
+        reqwest::StatusCode::BAD_REQUEST,
+        "failed to parse JSON",
+        "InvalidRequest",
+    )
+    .await;
+
+    // files provided along with the update
+    let form = update_allowlist_form(vec![other_address(Network::Regtest)], vec![])
+        .part("file_psbt", multipart::Part::bytes(signed_psbt_bytes(0)));
+    post_operation_expecting_error(
+        &ctx,
+        form,
+        reqwest::StatusCode::BAD_REQUEST,
+        "files are not allowed when updating the allowlist",
+        "InvalidRequest",
+    )
+    .await;
+
+    // update provided for another operation type
+    let update_part = multipart::Part::text("{}").mime_str(JSON).unwrap();
+    let form = send_form(OperationType::SendBtc, true).part("allowlist_update", update_part);
+    post_operation_expecting_error(
+        &ctx,
+        form,
+        reqwest::StatusCode::BAD_REQUEST,
+        "allowlist update is only allowed when updating the allowlist",
+        "InvalidRequest",
+    )
+    .await;
+
+    // invalid updates
+    let test_cases = [
+        (vec![], vec![], "empty allowlist update", "InvalidRequest"),
+        (
+            vec![s!("address")],
+            vec![],
+            "'address' is not a valid address",
+            "InvalidRequest",
+        ),
+        (
+            vec![other_address(Network::Bitcoin)],
+            vec![],
+            "does not belong to the Regtest network",
+            "NetworkMismatch",
+        ),
+        (
+            vec![other_address(Network::Regtest)],
+            vec![other_address(Network::Regtest)],
+            "appears more than once",
+            "InvalidRequest",
+        ),
+        (
+            vec![recipient_address.clone()],
+            vec![],
+            "is already in the allowlist",
+            "InvalidRequest",
+        ),
+        (
+            vec![],
+            vec![other_address(Network::Regtest)],
+            "is not in the allowlist",
+            "InvalidRequest",
+        ),
+    ];
+    for (add, remove, expected_message, expected_name) in test_cases {
+        post_operation_expecting_error(
+            &ctx,
+            update_allowlist_form(add, remove),
+            reqwest::StatusCode::BAD_REQUEST,
+            expected_message,
+            expected_name,
+        )
+        .await;
+    }
+
+    // PSBT provided when responding
+    let form = update_allowlist_form(vec![], vec![recipient_address]);
+    let operation_idx = post_operation_with_multipart_form(&ctx, form, 0)
+        .await
+        .operation_idx;
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/respondtooperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "PSBT file is not allowed when updating the allowlist",
+        "InvalidRequest",
+    )
+    .await;
+}
@@ -0,0 +1,150 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/approval_delay/";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let approval_delay = 2;
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.approval_delays = HashMap::from([(OperationType::SendBtc, approval_delay)]);
+    })
+    .await;
+
+    // reaching the threshold starts the approval delay
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert!(res.delay_ends_at.is_none());
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(2)));
+    let res = respond_to_operation(&ctx, form, 2).await;
+    assert_eq!(res.status, OperationStatus::ApprovedPendingDelay);
+    let delay_ends_at = res.delay_ends_at.unwrap();
+    assert!(delay_ends_at >= now().unix_timestamp() + approval_delay as i64 - 1);
+    assert!(res.files.iter().all(|f| f.r#type != FileType::CombinedPsbt));
+
+    // operation in its approval delay cannot be processed nor followed by a new one
+    let req = MarkOperationProcessedRequest {
+        operation_idx,
+        result: None,
+    };
+    let res = reqwest::Client::new()
+        .post(format!(
+            "http://{}/markoperationprocessed",
+            ctx.node_address
+        ))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "an operation in its approval delay cannot be marked as processed",
+        "CannotMarkOperationProcessed",
+    )
+    .await;
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_consignment", multipart::Part::bytes(vec![1, 2, 3]));
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/postoperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot post new operation: another operation is still pending",
+        "CannotPostNewOperation",
+    )
+    .await;
+
+    // operation is approved once the delay has passed
+    tokio::time::sleep(Duration::from_secs(approval_delay * 2 + 1)).await;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Approved);
+    assert_eq!(res.delay_ends_at, Some(delay_ends_at));
+    assert!(res.files.iter().any(|f| f.r#type == FileType::CombinedPsbt));
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // operation types without a delay are approved right away
+    let operation_idx = post_operation(&ctx, OperationType::CreateUtxos)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..3 {
+        let form =
+            respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(cosigner_idx)));
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Approved);
+    assert!(res.delay_ends_at.is_none());
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn uncombinable_psbts() {
+    let app_dir = format!("{TEST_DIR_BASE}uncombinable_psbts");
+
+    let approval_delay = 2;
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.approval_delays = HashMap::from([(OperationType::SendBtc, approval_delay)]);
+    })
+    .await;
+
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..3 {
+        let form =
+            respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(cosigner_idx)));
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::ApprovedPendingDelay);
+
+    // replace a stored ACK PSBT with one spending a different transaction
+    let psbt_file = res
+        .files
+        .iter()
+        .find(|f| {
+            f.r#type == FileType::Psbt && f.posted_by_xpub.as_ref() == Some(&ctx.cosigners[2].0)
+        })
+        .unwrap();
+    let mut psbt = test_psbt();
+    psbt.unsigned_tx.output[0].value = Amount::from_sat(2000);
+    std::fs::write(
+        Path::new(&app_dir).join(FILES_DIR).join(&psbt_file.file_id),
+        psbt.serialize(),
+    )
+    .unwrap();
+
+    // operation is discarded once the delay has passed, not left in its delay
+    tokio::time::sleep(Duration::from_secs(approval_delay * 2 + 1)).await;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Discarded);
+    assert!(res.files.iter().all(|f| f.r#type != FileType::CombinedPsbt));
+}
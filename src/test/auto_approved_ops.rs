@@ -0,0 +1,56 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/auto_approved_ops/";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.threshold_vanilla = 2;
+        params.threshold_colored = 3;
+        params.auto_approved_ops = vec![OperationType::CreateUtxos, OperationType::BlindReceive];
+    })
+    .await;
+
+    // auto-approved types are reported by the info API
+    let res = info(&ctx, None).await;
+    assert_eq!(
+        res.auto_approved_ops,
+        vec![OperationType::CreateUtxos, OperationType::BlindReceive]
+    );
+
+    // configured types are approved on posting
+    for operation_type in [OperationType::CreateUtxos, OperationType::BlindReceive] {
+        let operation_idx = post_operation(&ctx, operation_type).await.operation_idx;
+        let res = get_operation_by_idx(&ctx, operation_idx, None)
+            .await
+            .unwrap();
+        assert_eq!(res.status, OperationStatus::Approved);
+        assert!(res.threshold.is_none());
+        for cosigner_idx in 0..ctx.num_cosigners() {
+            mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+        }
+    }
+
+    // other types require approval, with the colored threshold for receives and issuances
+    let operation_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert_eq!(res.threshold, Some(3));
+    for cosigner_idx in 1..3 {
+        let form =
+            respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(cosigner_idx)));
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Approved);
+}
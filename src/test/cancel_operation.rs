@@ -0,0 +1,139 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/cancel_operation/";
+
+const PATH: &str = "canceloperation";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let (ctx, operation_idx) = setup_with_pending_operation(&app_dir).await;
+
+    // a NACK from another cosigner doesn't prevent cancellation
+    let form = respond_to_operation_form(operation_idx, false, None);
+    respond_to_operation(&ctx, form, 1).await;
+
+    // cancel the operation as its initiator
+    let res = cancel_operation(&ctx, operation_idx, 0).await;
+    assert_eq!(res.operation_idx, operation_idx);
+    assert_eq!(res.status, OperationStatus::Cancelled);
+    assert_eq!(res.my_response, Some(true));
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Cancelled);
+
+    // all cosigners can mark the cancelled operation as processed
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+        let res = get_last_processed_op_idx(&ctx, cosigner_idx).await;
+        assert_eq!(res.operation_idx, operation_idx);
+    }
+
+    // a new operation can be posted
+    let res = post_operation(&ctx, OperationType::SendRgb).await;
+    assert_eq!(res.operation_idx, operation_idx + 1);
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let (ctx, operation_idx) = setup_with_pending_operation(&app_dir).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::POST,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: false,
+        },
+    )
+    .await;
+
+    // JSON body checks
+    json_body_checks(&ctx, api_info.clone()).await;
+
+    // non-existent operation
+    let req = CancelOperationRequest {
+        operation_idx: 9999,
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "Operation not found",
+        "OperationNotFound",
+    )
+    .await;
+
+    // not the initiator
+    let req = CancelOperationRequest { operation_idx };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot cancel operation: only the initiator can cancel an operation",
+        "CannotCancelOperation",
+    )
+    .await;
+
+    // already ACKed by another cosigner
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    respond_to_operation(&ctx, form, 1).await;
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot cancel operation: operation has already been ACKed by another cosigner",
+        "CannotCancelOperation",
+    )
+    .await;
+
+    // operation not pending
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(2)));
+    let res = respond_to_operation(&ctx, form, 2).await;
+    assert_eq!(res.status, OperationStatus::Approved);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot cancel operation: operation is not pending",
+        "CannotCancelOperation",
+    )
+    .await;
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/cosigner_roles/";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.cosigner_roles = HashMap::from([(
+            params.cosigner_xpubs[3].clone(),
+            CosignerRolesConfig { veto: true },
+        )]);
+    })
+    .await;
+    let veto_holder_xpub = ctx.cosigners[3].0.clone();
+
+    // veto holders are reported in the operation details
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.veto_holders, HashSet::from([veto_holder_xpub.clone()]));
+
+    // a NACK from the veto holder discards the operation despite the ACKs
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = respond_to_operation(&ctx, form, 3).await;
+    assert_eq!(res.status, OperationStatus::Discarded);
+    assert_eq!(res.acked_by.len(), 2);
+    assert_eq!(res.nacked_by, HashSet::from([veto_holder_xpub]));
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // a NACK from a cosigner without the veto role doesn't discard the operation and an ACK
+    // from the veto holder counts as a regular one
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(3)));
+    let res = respond_to_operation(&ctx, form, 3).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(2)));
+    let res = respond_to_operation(&ctx, form, 2).await;
+    assert_eq!(res.status, OperationStatus::Approved);
+
+    // no veto holders by default
+    let app_dir = format!("{TEST_DIR_BASE}success_no_roles");
+    let ctx = setup_daemon(&app_dir).await;
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert!(res.veto_holders.is_empty());
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn restart() {
+    let app_dir = format!("{TEST_DIR_BASE}restart");
+
+    let mut app_params = None;
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.cosigner_roles = HashMap::from([(
+            params.cosigner_xpubs[3].clone(),
+            CosignerRolesConfig { veto: true },
+        )]);
+        app_params = Some(params.clone());
+    })
+    .await;
+    let app_params = app_params.unwrap();
+    // wait for the first start to complete
+    info(&ctx, None).await;
+
+    // restarting with the same roles succeeds
+    crate::startup::start_daemon(&app_params).await.unwrap();
+
+    // roles cannot be changed after the first start
+    let changed_roles = [
+        HashMap::new(),
+        HashMap::from([(
+            app_params.cosigner_xpubs[2].clone(),
+            CosignerRolesConfig { veto: true },
+        )]),
+        HashMap::from([
+            (
+                app_params.cosigner_xpubs[2].clone(),
+                CosignerRolesConfig { veto: true },
+            ),
+            (
+                app_params.cosigner_xpubs[3].clone(),
+                CosignerRolesConfig { veto: true },
+            ),
+        ]),
+    ];
+    for cosigner_roles in changed_roles {
+        let mut params = app_params.clone();
+        params.cosigner_roles = cosigner_roles;
+        let result = crate::startup::start_daemon(&params).await;
+        assert!(matches!(result, Err(AppError::CannotChangeCosigners)));
+    }
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/cosigner_weights/";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.cosigner_weights = vec![1, 1, 1, 2];
+    })
+    .await;
+
+    // weights are reported by the info API
+    let res = info(&ctx, None).await;
+    let weights: Vec<u8> = res.cosigners.iter().map(|c| c.weight).collect();
+    assert_eq!(weights, vec![1, 1, 1, 2]);
+
+    // the initiator's ACK counts with its weight
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.acked_weight, 1);
+    assert_eq!(res.nacked_weight, 0);
+    assert_eq!(res.total_weight, 5);
+    assert_eq!(res.threshold, Some(3));
+
+    // an ACK from a heavier cosigner reaches the threshold
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(3)));
+    let res = respond_to_operation(&ctx, form, 3).await;
+    assert_eq!(res.status, OperationStatus::Approved);
+    assert_eq!(res.acked_by.len(), 2);
+    assert_eq!(res.acked_weight, 3);
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // the operation is discarded once the NACKed weight makes the threshold unreachable
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = respond_to_operation(&ctx, form, 3).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert_eq!(res.nacked_weight, 2);
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Discarded);
+    assert_eq!(res.acked_weight, 1);
+    assert_eq!(res.nacked_weight, 3);
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn initiator_reaches_threshold() {
+    let app_dir = format!("{TEST_DIR_BASE}initiator_reaches_threshold");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.cosigner_weights = vec![3, 1, 1, 1];
+        params.threshold_vanilla = 3;
+        params.threshold_colored = 3;
+        params.approval_delays = HashMap::from([(OperationType::SendRgb, 3600)]);
+    })
+    .await;
+
+    // the initiator's ACK alone approves the operation when posting it
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Approved);
+    assert_eq!(res.acked_weight, 3);
+    assert!(res.files.iter().any(|f| f.r#type == FileType::CombinedPsbt));
+    let mut res = subscribe_events(&ctx, None, Some(0)).await;
+    let events = read_events(&mut res, 2).await;
+    let event_types: Vec<_> = events.iter().map(|e| e.event_type).collect();
+    assert_eq!(
+        event_types,
+        vec![EventType::OperationPosted, EventType::OperationApproved]
+    );
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // or starts its approval delay
+    let operation_idx = post_operation(&ctx, OperationType::SendRgb)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::ApprovedPendingDelay);
+    assert!(res.delay_ends_at.is_some());
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/events/";
+
+const PATH: &str = "events";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    // subscribe before any event and follow an operation's lifecycle
+    let mut res = subscribe_events(&ctx, None, Some(3)).await;
+    let operation_idx = post_operation(&ctx, OperationType::SendRgb)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..=2 {
+        let form =
+            respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(cosigner_idx)));
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    mark_operation_processed(&ctx, operation_idx, 0).await;
+    let events = read_events(&mut res, 5).await;
+    let expected = [
+        (EventType::OperationPosted, Some(0)),
+        (EventType::CosignerResponded, Some(1)),
+        (EventType::CosignerResponded, Some(2)),
+        (EventType::OperationApproved, None),
+        (EventType::CosignerProcessed, Some(0)),
+    ];
+    for (i, (event, (event_type, cosigner_idx))) in events.iter().zip(expected).enumerate() {
+        assert_eq!(event.event_idx, i as i32 + 1);
+        assert_eq!(event.event_type, event_type);
+        assert_eq!(event.operation_idx, operation_idx);
+        assert_eq!(
+            event.cosigner_xpub,
+            cosigner_idx.map(|idx: usize| ctx.cosigners[idx].0.clone())
+        );
+    }
+
+    // resume from an event ID as watch-only, getting stored events then new ones
+    let mut res = subscribe_events(&ctx, Some(3), None).await;
+    let events = read_events(&mut res, 2).await;
+    assert_eq!(events[0].event_idx, 4);
+    assert_eq!(events[0].event_type, EventType::OperationApproved);
+    assert_eq!(events[1].event_idx, 5);
+    assert_eq!(events[1].event_type, EventType::CosignerProcessed);
+    let operation_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+    let events = read_events(&mut res, 2).await;
+    assert_eq!(events[0].event_idx, 6);
+    assert_eq!(events[0].event_type, EventType::OperationPosted);
+    assert_eq!(events[0].operation_idx, operation_idx);
+    assert_eq!(events[1].event_idx, 7);
+    assert_eq!(events[1].event_type, EventType::OperationApproved);
+    assert_eq!(events[1].operation_idx, operation_idx);
+
+    // subscribe without an event ID, getting all stored events
+    let mut res = subscribe_events(&ctx, None, Some(1)).await;
+    let events = read_events(&mut res, 7).await;
+    let event_idxs: Vec<i32> = events.iter().map(|e| e.event_idx).collect();
+    assert_eq!(event_idxs, (1..=7).collect::<Vec<i32>>());
+
+    // cancelled operations emit an event as well
+    let mut res = subscribe_events(&ctx, Some(7), Some(1)).await;
+    mark_operation_processed(&ctx, operation_idx, 0).await;
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    cancel_operation(&ctx, operation_idx, 0).await;
+    let events = read_events(&mut res, 3).await;
+    assert_eq!(events[2].event_type, EventType::OperationCancelled);
+    assert_eq!(events[2].operation_idx, operation_idx);
+    assert_eq!(events[2].cosigner_xpub, Some(ctx.cosigners[0].0.clone()));
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::GET,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: true,
+        },
+    )
+    .await;
+
+    // invalid Last-Event-ID header
+    for last_event_id in ["invalid", "-", "1.5"] {
+        let res = reqwest::Client::new()
+            .get(format!("http://{}/{}", ctx.node_address, PATH))
+            .bearer_auth(ctx.get_cosigner_token(0))
+            .header("Last-Event-ID", last_event_id)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_nok(
+            res,
+            reqwest::StatusCode::BAD_REQUEST,
+            "invalid Last-Event-ID header",
+            "InvalidRequest",
+        )
+        .await;
+    }
+}
@@ -30,17 +30,17 @@ async fn success() {
     );
     let body = res.bytes().await.unwrap();
     assert!(!body.is_empty());
-    assert_eq!(&body[..], b"psbt");
+    assert_eq!(body.to_vec(), signed_psbt_bytes(0));
 
     // get file with a different cosigner
     let res = get_file(&ctx, file_id.clone(), Some(1)).await;
     let body = res.bytes().await.unwrap();
-    assert_eq!(&body[..], b"psbt");
+    assert_eq!(body.to_vec(), signed_psbt_bytes(0));
 
     // get file with a watch-only wallet
     let res = get_file(&ctx, file_id, None).await;
     let body = res.bytes().await.unwrap();
-    assert_eq!(&body[..], b"psbt");
+    assert_eq!(body.to_vec(), signed_psbt_bytes(0));
 }
 
 #[serial_test::serial]
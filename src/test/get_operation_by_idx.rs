@@ -17,16 +17,17 @@ async fn success() {
         .await
         .unwrap();
     assert_eq!(res.operation_idx, operation_idx);
-    assert_eq!(res.initiator_xpub, "xpub0");
+    assert_eq!(res.initiator_xpub, ctx.cosigners[0].0);
     assert_eq!(res.operation_type, OperationType::SendRgb);
     assert_eq!(res.my_response, Some(true));
+    assert!(res.expires_at.is_none());
 
     // get operation as different cosigner
     let res = get_operation_by_idx(&ctx, operation_idx, Some(1))
         .await
         .unwrap();
     assert_eq!(res.operation_idx, operation_idx);
-    assert_eq!(res.initiator_xpub, "xpub0");
+    assert_eq!(res.initiator_xpub, ctx.cosigners[0].0);
     assert_eq!(res.operation_type, OperationType::SendRgb);
     assert!(res.my_response.is_none());
 
@@ -35,14 +36,99 @@ async fn success() {
         .await
         .unwrap();
     assert_eq!(res.operation_idx, operation_idx);
-    assert_eq!(res.initiator_xpub, "xpub0");
+    assert_eq!(res.initiator_xpub, ctx.cosigners[0].0);
     assert!(res.my_response.is_none());
 
+    // the initiator's PSBT is decoded for watch-only too
+    let summary = res.psbt_summary.unwrap();
+    assert_eq!(summary.inputs.len(), 1);
+    assert_eq!(
+        summary.inputs[0].outpoint,
+        format!("{}:0", Txid::from_byte_array([1; 32]))
+    );
+    assert_eq!(summary.inputs[0].amount_sat, Some(2000));
+    assert_eq!(summary.outputs.len(), 2);
+    assert!(
+        summary.outputs[0]
+            .address
+            .as_ref()
+            .unwrap()
+            .starts_with("bcrt1q")
+    );
+    assert_eq!(summary.outputs[0].amount_sat, 1000);
+    assert!(!summary.outputs[0].is_change);
+    assert_eq!(summary.outputs[1].amount_sat, 500);
+    assert!(summary.outputs[1].is_change);
+    assert_eq!(summary.fee_sat, Some(500));
+    // 862 WU once signed by 3 cosigners, rounded up to 216 vB
+    assert_eq!(summary.fee_rate_sat_per_vb, Some(500.0 / 216.0));
+
     // get non-existent operation
     let res = get_operation_by_idx(&ctx, 9999, Some(0)).await;
     assert!(res.is_none());
 }
 
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn wait() {
+    let app_dir = format!("{TEST_DIR_BASE}wait");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    // times out if the operation doesn't exist yet
+    let start = Instant::now();
+    let res = wait_for_operation(&ctx, 1, None, 1).await;
+    assert!(res.is_none());
+    assert!(start.elapsed() >= Duration::from_secs(1));
+
+    // returns as soon as the operation gets posted
+    let start = Instant::now();
+    let (res, posted) = tokio::join!(wait_for_operation(&ctx, 1, None, 10), async {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        post_operation(&ctx, OperationType::SendRgb).await
+    });
+    let res = res.unwrap();
+    assert_eq!(res.operation_idx, posted.operation_idx);
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert!(start.elapsed() >= Duration::from_millis(500));
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    // returns right away if the status differs from the provided one
+    let start = Instant::now();
+    let res = wait_for_operation(&ctx, 1, Some(OperationStatus::Approved), 10)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    // keeps waiting on responses not changing the status, returns once it changes
+    let start = Instant::now();
+    let (res, _) = tokio::join!(
+        wait_for_operation(&ctx, 1, Some(OperationStatus::Pending), 10),
+        async {
+            for cosigner_idx in 2..=3 {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                let form = respond_to_operation_form(1, false, None);
+                respond_to_operation(&ctx, form, cosigner_idx).await;
+            }
+        }
+    );
+    let res = res.unwrap();
+    assert_eq!(res.status, OperationStatus::Discarded);
+    assert_eq!(res.nacked_by.len(), 2);
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    // times out if the status doesn't change
+    let start = Instant::now();
+    let res = wait_for_operation(&ctx, 1, Some(OperationStatus::Discarded), 1)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Discarded);
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
 #[serial_test::serial]
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[traced_test]
@@ -68,4 +154,25 @@ async fn fail() {
 
     // JSON body checks
     json_body_checks(&ctx, api_info.clone()).await;
+
+    // wait too long
+    let req = GetOperationByIdxRequest {
+        operation_idx: 1,
+        wait: Some(MAX_WAIT_SECS + 1),
+        status: None,
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "wait cannot exceed 60 seconds",
+        "InvalidRequest",
+    )
+    .await;
 }
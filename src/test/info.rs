@@ -19,6 +19,19 @@ async fn success() {
         assert_eq!(res.rgb_lib_version, ctx.rgb_lib_version);
     }
 
+    // network and cosigners info
+    let res = info(&ctx, Some(0)).await;
+    assert_eq!(res.network, BitcoinNetwork::Regtest);
+    assert_eq!(res.cosigners.len(), ctx.cosigners.len());
+    for (cosigner, (xpub, _)) in res.cosigners.iter().zip(&ctx.cosigners) {
+        assert_eq!(&cosigner.xpub, xpub);
+        let key = Xpub::from_str(xpub).unwrap();
+        assert_eq!(cosigner.fingerprint, key.fingerprint().to_string());
+    }
+    assert_eq!(res.auto_approved_ops, DEFAULT_AUTO_APPROVED_OPS);
+    assert!(res.address_allowlist.is_empty());
+    assert!(res.spending_limits.is_empty());
+
     // watch-only
     let res = info(&ctx, None).await;
     assert_eq!(res.min_rgb_lib_version, MIN_RGB_LIB_VERSION);
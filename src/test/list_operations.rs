@@ -0,0 +1,256 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/list_operations/";
+
+const PATH: &str = "listoperations";
+
+fn operation_idxs(res: &ListOperationsResponse) -> Vec<i32> {
+    res.operations.iter().map(|o| o.operation_idx).collect()
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    // no operations yet
+    let req = ListOperationsRequest {
+        limit: 10,
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, None).await;
+    assert!(res.operations.is_empty());
+    assert!(res.next_cursor.is_none());
+
+    // a discarded operation, processed by everyone
+    let discarded_op_idx = post_operation(&ctx, OperationType::SendRgb)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..=2 {
+        let form = respond_to_operation_form(discarded_op_idx, false, None);
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, discarded_op_idx, cosigner_idx).await;
+    }
+
+    // an auto-approved operation posted by another cosigner, processed by cosigners 0 and 1
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::Issuance as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(1));
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let approved_op_idx = post_operation_with_multipart_form(&ctx, form, 1)
+        .await
+        .operation_idx;
+    for cosigner_idx in 0..=1 {
+        mark_operation_processed(&ctx, approved_op_idx, cosigner_idx).await;
+    }
+
+    // a pending operation
+    let pending_op_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+
+    // all operations, in index order
+    let res = list_operations(&ctx, &req, None).await;
+    assert_eq!(
+        operation_idxs(&res),
+        vec![discarded_op_idx, approved_op_idx, pending_op_idx]
+    );
+    assert!(res.next_cursor.is_none());
+    assert_eq!(res.operations[0].status, OperationStatus::Discarded);
+    assert!(res.operations.iter().all(|o| o.my_response.is_none()));
+
+    // cosigners get their responses
+    let res = list_operations(&ctx, &req, Some(2)).await;
+    assert_eq!(res.operations[0].my_response, Some(false));
+    assert!(res.operations[0].processed_at.is_some());
+    assert!(res.operations[1].processed_at.is_none());
+
+    // pagination
+    let mut req = ListOperationsRequest {
+        limit: 2,
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, None).await;
+    assert_eq!(
+        operation_idxs(&res),
+        vec![discarded_op_idx, approved_op_idx]
+    );
+    assert_eq!(res.next_cursor, Some(approved_op_idx));
+    req.cursor = res.next_cursor;
+    let res = list_operations(&ctx, &req, None).await;
+    assert_eq!(operation_idxs(&res), vec![pending_op_idx]);
+    assert!(res.next_cursor.is_none());
+
+    // filter by status
+    let req = ListOperationsRequest {
+        limit: 10,
+        status: Some(OperationStatus::Pending),
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, None).await;
+    assert_eq!(operation_idxs(&res), vec![pending_op_idx]);
+
+    // filter by type
+    let req = ListOperationsRequest {
+        limit: 10,
+        operation_type: Some(OperationType::Issuance),
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, None).await;
+    assert_eq!(operation_idxs(&res), vec![approved_op_idx]);
+
+    // filter by initiator
+    let req = ListOperationsRequest {
+        limit: 10,
+        initiator_xpub: Some(ctx.cosigners[0].0.clone()),
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, None).await;
+    assert_eq!(operation_idxs(&res), vec![discarded_op_idx, pending_op_idx]);
+
+    // filter by creation time
+    let first_created_at = get_operation_by_idx(&ctx, discarded_op_idx, None)
+        .await
+        .unwrap()
+        .created_at;
+    let last_created_at = get_operation_by_idx(&ctx, pending_op_idx, None)
+        .await
+        .unwrap()
+        .created_at;
+    let mut req = ListOperationsRequest {
+        limit: 10,
+        created_from: Some(first_created_at),
+        created_to: Some(last_created_at),
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, None).await;
+    assert_eq!(res.operations.len(), 3);
+    req.created_from = Some(last_created_at + 1);
+    let res = list_operations(&ctx, &req, None).await;
+    assert!(res.operations.is_empty());
+    req.created_from = None;
+    req.created_to = Some(first_created_at - 1);
+    let res = list_operations(&ctx, &req, None).await;
+    assert!(res.operations.is_empty());
+
+    // filter by operations not yet processed by the requesting cosigner
+    let req = ListOperationsRequest {
+        limit: 10,
+        unprocessed_by_me: true,
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, Some(0)).await;
+    assert_eq!(operation_idxs(&res), vec![pending_op_idx]);
+    let res = list_operations(&ctx, &req, Some(2)).await;
+    assert_eq!(operation_idxs(&res), vec![approved_op_idx, pending_op_idx]);
+
+    // filters are combined
+    let req = ListOperationsRequest {
+        limit: 10,
+        status: Some(OperationStatus::Approved),
+        unprocessed_by_me: true,
+        ..Default::default()
+    };
+    let res = list_operations(&ctx, &req, Some(2)).await;
+    assert_eq!(operation_idxs(&res), vec![approved_op_idx]);
+    let res = list_operations(&ctx, &req, Some(1)).await;
+    assert!(res.operations.is_empty());
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::POST,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: true,
+        },
+    )
+    .await;
+
+    // JSON body checks
+    json_body_checks(&ctx, api_info.clone()).await;
+
+    // invalid limit
+    for limit in [0, MAX_LIST_OPERATIONS_LIMIT + 1] {
+        let req = ListOperationsRequest {
+            limit,
+            ..Default::default()
+        };
+        let res = reqwest::Client::new()
+            .post(format!("http://{}/{}", ctx.node_address, PATH))
+            .bearer_auth(ctx.get_cosigner_token(0))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_nok(
+            res,
+            reqwest::StatusCode::BAD_REQUEST,
+            "limit must be between 1 and 100",
+            "InvalidRequest",
+        )
+        .await;
+    }
+
+    // unknown initiator xPub
+    let req = ListOperationsRequest {
+        limit: 10,
+        initiator_xpub: Some(cosigner_xpub(99)),
+        ..Default::default()
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "unknown initiator xPub",
+        "InvalidRequest",
+    )
+    .await;
+
+    // unprocessed_by_me as watch-only
+    let req = ListOperationsRequest {
+        limit: 10,
+        unprocessed_by_me: true,
+        ..Default::default()
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(&ctx.watch_only_token)
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "unprocessed_by_me requires a cosigner token",
+        "InvalidRequest",
+    )
+    .await;
+}
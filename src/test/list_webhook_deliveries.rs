@@ -0,0 +1,253 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/list_webhook_deliveries/";
+
+const PATH: &str = "listwebhookdeliveries";
+
+fn list_request(status: Option<WebhookDeliveryStatus>) -> ListWebhookDeliveriesRequest {
+    ListWebhookDeliveriesRequest {
+        limit: MAX_LIST_WEBHOOK_DELIVERIES_LIMIT,
+        status,
+        ..Default::default()
+    }
+}
+
+async fn wait_for_delivered(ctx: &TestContext, count: usize) -> Vec<WebhookDeliveryResponse> {
+    tokio::time::timeout(WEBHOOKS_TIMEOUT, async {
+        loop {
+            let res =
+                list_webhook_deliveries(ctx, &list_request(Some(WebhookDeliveryStatus::Delivered)))
+                    .await;
+            if res.deliveries.len() >= count {
+                return res.deliveries;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for webhook deliveries")
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let receiver = start_webhook_receiver(0).await;
+    let flaky_receiver = start_webhook_receiver(1).await;
+    let webhooks = vec![
+        WebhookConfig {
+            url: receiver.url.clone(),
+            secret: s!("secret"),
+        },
+        WebhookConfig {
+            url: flaky_receiver.url.clone(),
+            secret: s!("flaky_secret"),
+        },
+    ];
+    let ctx = setup_daemon_with_params(&app_dir, |params| params.webhooks = webhooks).await;
+
+    // no deliveries before any operation
+    let res = list_webhook_deliveries(&ctx, &list_request(None)).await;
+    assert!(res.deliveries.is_empty());
+
+    // post an operation and have it discarded
+    let discarded_op_idx = post_operation(&ctx, OperationType::SendRgb)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..=2 {
+        let form = respond_to_operation_form(discarded_op_idx, false, None);
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+
+    // post an auto-approved operation
+    mark_operation_processed(&ctx, discarded_op_idx, 0).await;
+    let approved_op_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+
+    // wait for all deliveries to both webhooks, including a retry for the flaky one
+    let deliveries = wait_for_delivered(&ctx, 8).await;
+    let res = list_webhook_deliveries(&ctx, &list_request(None)).await;
+    assert_eq!(res.deliveries.len(), 8);
+    for delivery in &deliveries {
+        assert!(delivery.delivered_at.is_some());
+        assert!(delivery.last_error.is_none());
+    }
+    let flaky_attempts: u32 = deliveries
+        .iter()
+        .filter(|d| d.url == flaky_receiver.url)
+        .map(|d| d.attempts)
+        .sum();
+    assert_eq!(flaky_attempts, 5);
+    let delivery_idxs: Vec<i32> = res.deliveries.iter().map(|d| d.delivery_idx).collect();
+    assert!(res.next_cursor.is_none());
+
+    // pagination
+    let mut req = ListWebhookDeliveriesRequest {
+        limit: 5,
+        ..Default::default()
+    };
+    let res = list_webhook_deliveries(&ctx, &req).await;
+    assert_eq!(
+        res.deliveries
+            .iter()
+            .map(|d| d.delivery_idx)
+            .collect::<Vec<_>>(),
+        delivery_idxs[..5]
+    );
+    assert_eq!(res.next_cursor, Some(delivery_idxs[4]));
+    req.cursor = res.next_cursor;
+    let res = list_webhook_deliveries(&ctx, &req).await;
+    assert_eq!(
+        res.deliveries
+            .iter()
+            .map(|d| d.delivery_idx)
+            .collect::<Vec<_>>(),
+        delivery_idxs[5..]
+    );
+    assert!(res.next_cursor.is_none());
+
+    // filter by status
+    let res =
+        list_webhook_deliveries(&ctx, &list_request(Some(WebhookDeliveryStatus::Pending))).await;
+    assert!(res.deliveries.is_empty());
+    let res =
+        list_webhook_deliveries(&ctx, &list_request(Some(WebhookDeliveryStatus::Failed))).await;
+    assert!(res.deliveries.is_empty());
+
+    // check the received payloads and their signatures
+    for (receiver, secret) in [(&receiver, "secret"), (&flaky_receiver, "flaky_secret")] {
+        let mut payloads = Vec::new();
+        for (headers, body) in receiver.requests() {
+            let signature = headers
+                .get(WEBHOOK_SIGNATURE_HEADER)
+                .unwrap()
+                .to_str()
+                .unwrap();
+            assert_eq!(
+                signature,
+                format!("sha256={}", sign_webhook_payload(secret, &body))
+            );
+            assert_ne!(
+                signature,
+                format!("sha256={}", sign_webhook_payload("wrong", &body))
+            );
+            payloads.push(serde_json::from_slice::<WebhookPayload>(&body).unwrap());
+        }
+        payloads.sort_by_key(|p| p.event.event_idx);
+        let received: Vec<(EventType, i32)> = payloads
+            .iter()
+            .map(|p| (p.event.event_type, p.operation.operation_idx))
+            .collect();
+        assert_eq!(
+            received,
+            vec![
+                (EventType::OperationPosted, discarded_op_idx),
+                (EventType::OperationDiscarded, discarded_op_idx),
+                (EventType::OperationPosted, approved_op_idx),
+                (EventType::OperationApproved, approved_op_idx),
+            ]
+        );
+        assert_eq!(
+            payloads[0].event.cosigner_xpub,
+            Some(ctx.cosigners[0].0.clone())
+        );
+        // operations are reported as they were when the event occurred
+        assert_eq!(payloads[0].operation.status, OperationStatus::Pending);
+        assert!(payloads[0].operation.nacked_by.is_empty());
+        assert_eq!(payloads[1].event.cosigner_xpub, None);
+        assert_eq!(payloads[1].operation.status, OperationStatus::Discarded);
+        assert_eq!(payloads[3].operation.status, OperationStatus::Approved);
+    }
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    // webhook pointing to a closed port
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let unreachable_url = format!("http://{}/webhook", listener.local_addr().unwrap());
+    drop(listener);
+    let webhooks = vec![WebhookConfig {
+        url: unreachable_url.clone(),
+        secret: s!("secret"),
+    }];
+    let ctx = setup_daemon_with_params(&app_dir, |params| params.webhooks = webhooks).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::POST,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: false,
+        },
+    )
+    .await;
+
+    // invalid limit
+    for limit in [0, MAX_LIST_WEBHOOK_DELIVERIES_LIMIT + 1] {
+        let req = ListWebhookDeliveriesRequest {
+            limit,
+            ..Default::default()
+        };
+        let res = reqwest::Client::new()
+            .post(format!("http://{}/{}", ctx.node_address, PATH))
+            .bearer_auth(ctx.get_cosigner_token(0))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_nok(
+            res,
+            reqwest::StatusCode::BAD_REQUEST,
+            "limit must be between 1 and 100",
+            "InvalidRequest",
+        )
+        .await;
+    }
+
+    // invalid status
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&serde_json::json!({ "limit": 10, "status": "Unknown" }))
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "Failed to deserialize the JSON body",
+        "InvalidRequest",
+    )
+    .await;
+
+    // failed attempts are recorded and the delivery is kept pending for a retry
+    post_operation(&ctx, OperationType::SendRgb).await;
+    let delivery = tokio::time::timeout(WEBHOOKS_TIMEOUT, async {
+        loop {
+            let res = list_webhook_deliveries(&ctx, &list_request(None)).await;
+            if let Some(delivery) = res.deliveries.into_iter().find(|d| d.attempts > 0) {
+                return delivery;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for a webhook delivery attempt");
+    assert_eq!(delivery.url, unreachable_url);
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert!(delivery.last_error.unwrap().contains("request failed"));
+    assert!(delivery.delivered_at.is_none());
+    assert!(delivery.next_attempt_at > now().unix_timestamp() - 1);
+}
@@ -43,6 +43,71 @@ async fn success() {
     }
 }
 
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn result() {
+    let app_dir = format!("{TEST_DIR_BASE}result");
+
+    let (ctx, operation_idx) = setup_with_approved_operation(&app_dir).await;
+    let result = ProcessingResult {
+        success: true,
+        txid: Some(Txid::from_byte_array([1; 32]).to_string()),
+        state_hash: "ab".repeat(32),
+    };
+
+    // no results reported yet
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert!(res.processing_reports.is_empty());
+    assert!(!res.divergent);
+
+    // the same result reported by 2 cosigners, the state hash is case-insensitive
+    mark_operation_processed_with_result(&ctx, operation_idx, 0, Some(result.clone())).await;
+    let uppercase_result = ProcessingResult {
+        state_hash: result.state_hash.to_uppercase(),
+        ..result.clone()
+    };
+    mark_operation_processed_with_result(&ctx, operation_idx, 1, Some(uppercase_result)).await;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.processing_reports.len(), 2);
+    assert_eq!(res.processing_reports[0].cosigner_xpub, ctx.cosigners[0].0);
+    assert_eq!(res.processing_reports[0].result, result);
+    assert_eq!(res.processing_reports[1].result, result);
+    assert!(!res.divergent);
+
+    // no result reported
+    mark_operation_processed(&ctx, operation_idx, 2).await;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.processing_reports.len(), 2);
+    assert!(!res.divergent);
+
+    // a different result flags the operation as divergent
+    // (posted, 2 responses, approved and 3 processed events so far)
+    let mut events_res = subscribe_events(&ctx, Some(7), None).await;
+    let diverging_result = ProcessingResult {
+        state_hash: "cd".repeat(32),
+        ..result.clone()
+    };
+    mark_operation_processed_with_result(&ctx, operation_idx, 3, Some(diverging_result.clone()))
+        .await;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.processing_reports.len(), 3);
+    assert_eq!(res.processing_reports[2].result, diverging_result);
+    assert!(res.divergent);
+    let events = read_events(&mut events_res, 2).await;
+    assert_eq!(events[0].event_type, EventType::CosignerProcessed);
+    assert_eq!(events[1].event_type, EventType::OperationDiverged);
+    assert_eq!(events[1].operation_idx, operation_idx);
+}
+
 #[serial_test::serial]
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[traced_test]
@@ -72,6 +137,7 @@ async fn fail() {
     // non-existent operation
     let req = MarkOperationProcessedRequest {
         operation_idx: 9999,
+        result: None,
     };
     let res = reqwest::Client::new()
         .post(format!("http://{}/{}", ctx.node_address, PATH))
@@ -89,11 +155,14 @@ async fn fail() {
     .await;
 
     // already marked as processed
-    let operation_idx = post_operation(&ctx, OperationType::Issuance)
+    let issuance_op_idx = post_operation(&ctx, OperationType::Issuance)
         .await
         .operation_idx;
-    mark_operation_processed(&ctx, operation_idx, 0).await;
-    let req = MarkOperationProcessedRequest { operation_idx };
+    mark_operation_processed(&ctx, issuance_op_idx, 0).await;
+    let req = MarkOperationProcessedRequest {
+        operation_idx: issuance_op_idx,
+        result: None,
+    };
     let res = reqwest::Client::new()
         .post(format!("http://{}/{}", ctx.node_address, PATH))
         .bearer_auth(ctx.get_cosigner_token(0))
@@ -113,7 +182,10 @@ async fn fail() {
     let operation_idx = post_operation(&ctx, OperationType::SendRgb)
         .await
         .operation_idx;
-    let req = MarkOperationProcessedRequest { operation_idx };
+    let req = MarkOperationProcessedRequest {
+        operation_idx,
+        result: None,
+    };
     let res = reqwest::Client::new()
         .post(format!("http://{}/{}", ctx.node_address, PATH))
         .bearer_auth(ctx.get_cosigner_token(0))
@@ -128,4 +200,83 @@ async fn fail() {
         "CannotMarkOperationProcessed",
     )
     .await;
+
+    let result = ProcessingResult {
+        success: true,
+        txid: None,
+        state_hash: "ab".repeat(32),
+    };
+
+    // invalid txid
+    let req = MarkOperationProcessedRequest {
+        operation_idx: issuance_op_idx,
+        result: Some(ProcessingResult {
+            txid: Some(s!("invalid")),
+            ..result.clone()
+        }),
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "invalid txid 'invalid'",
+        "InvalidRequest",
+    )
+    .await;
+
+    // invalid state hash
+    for state_hash in [s!("invalid"), "ab".repeat(31)] {
+        let req = MarkOperationProcessedRequest {
+            operation_idx: issuance_op_idx,
+            result: Some(ProcessingResult {
+                state_hash,
+                ..result.clone()
+            }),
+        };
+        let res = reqwest::Client::new()
+            .post(format!("http://{}/{}", ctx.node_address, PATH))
+            .bearer_auth(ctx.get_cosigner_token(1))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_nok(
+            res,
+            reqwest::StatusCode::BAD_REQUEST,
+            "state hash must be a 32-byte hex string",
+            "InvalidRequest",
+        )
+        .await;
+    }
+
+    // result for an operation that is not approved
+    for cosigner_idx in 1..=2 {
+        mark_operation_processed(&ctx, issuance_op_idx, cosigner_idx).await;
+        let form = respond_to_operation_form(operation_idx, false, None);
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    let req = MarkOperationProcessedRequest {
+        operation_idx,
+        result: Some(result.clone()),
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot mark operation as processed: a result can only be reported for an approved operation",
+        "CannotMarkOperationProcessed",
+    )
+    .await;
 }
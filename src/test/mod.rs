@@ -1,11 +1,28 @@
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     net::SocketAddr,
     path::Path,
-    sync::atomic::{AtomicU64, Ordering},
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
 use amplify::s;
 use biscuit_auth::{KeyPair, builder::date, macros::*};
+use bitcoin::{
+    Address, Amount, CompressedPublicKey, Network, NetworkKind, OutPoint, Psbt, ScriptBuf,
+    Transaction, TxIn, TxOut, Txid,
+    absolute::LockTime,
+    bip32::{ChildNumber, DerivationPath, Fingerprint, Xpriv, Xpub},
+    hashes::Hash,
+    opcodes::all::OP_CHECKMULTISIG,
+    script::Builder,
+    secp256k1::{PublicKey, Secp256k1},
+    transaction::Version,
+};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use futures_util::stream;
@@ -15,19 +32,37 @@ use tokio::net::TcpListener;
 use tracing_test::traced_test;
 
 use crate::routes::{
-    BumpAddressIndicesRequest, BumpAddressIndicesResponse, EmptyResponse, FileType,
-    GetCurrentAddressIndicesResponse, GetFileRequest, GetLastProcessedOpIdxResponse,
-    GetOperationByIdxRequest, InfoResponse, MarkOperationProcessedRequest, OperationResponse,
-    OperationStatus, OperationType, PostOperationResponse, RespondToOperationRequest,
+    AllowlistUpdate, BitcoinNetwork, BumpAddressIndicesRequest, BumpAddressIndicesResponse,
+    CancelOperationRequest, ChainStatus, DEFAULT_AUTO_APPROVED_OPS, EmptyResponse, EventResponse,
+    EventType, FileType, GetCurrentAddressIndicesResponse, GetFileRequest,
+    GetLastProcessedOpIdxResponse, GetOperationByIdxRequest, InfoResponse, ListOperationsRequest,
+    ListOperationsResponse, ListWebhookDeliveriesRequest, ListWebhookDeliveriesResponse,
+    MAX_LIST_OPERATIONS_LIMIT, MAX_LIST_WEBHOOK_DELIVERIES_LIMIT, MAX_SYNC_OPERATIONS,
+    MAX_WAIT_SECS, MarkOperationProcessedRequest, OperationResponse, OperationStatus,
+    OperationType, PostOperationResponse, ProcessingResult, RespondToOperationRequest,
+    RevokeTokenRequest, SYNC_ARCHIVE_FILES_DIR, SYNC_ARCHIVE_OPERATIONS_PATH, SpendingLimitWindow,
+    SyncOperationsRequest, SyncOperationsResponse, UpdateChainStatusRequest, VetoOperationRequest,
+    WebhookDeliveryResponse, WebhookDeliveryStatus, WebhookPayload,
+};
+use crate::startup::{
+    ChainSourceConfig, CosignerRolesConfig, FILES_DIR, MAX_RGB_LIB_VERSION, MIN_RGB_LIB_VERSION,
+    PolicyConfig, SpendingLimitConfig, WebhookConfig,
 };
-use crate::startup::{FILES_DIR, MAX_RGB_LIB_VERSION, MIN_RGB_LIB_VERSION};
+use crate::tasks::WEBHOOK_SIGNATURE_HEADER;
+use crate::utils::{now, sign_webhook_payload};
 
 use super::*;
 
 const JSON: &str = "application/json";
 const OCTET_STREAM: &str = "application/octet-stream";
 
-static COUNTER: AtomicU64 = AtomicU64::new(0);
+// BIP48 P2WSH account of the test cosigners
+const ACCOUNT_PATH: &str = "m/48'/1'/0'/2'";
+
+const NUM_COSIGNERS: u8 = 4;
+
+const EVENTS_TIMEOUT: Duration = Duration::from_secs(10);
+const WEBHOOKS_TIMEOUT: Duration = Duration::from_secs(20);
 
 #[derive(Debug, Deserialize, Serialize)]
 struct APIErrorBody {
@@ -66,6 +101,19 @@ enum Role {
     WatchOnly,
 }
 
+fn cosigner_master_xpriv(seed: u8) -> Xpriv {
+    Xpriv::new_master(NetworkKind::Test, &[seed; 32]).unwrap()
+}
+
+fn cosigner_xpub(seed: u8) -> String {
+    let secp = Secp256k1::new();
+    let account_path = DerivationPath::from_str(ACCOUNT_PATH).unwrap();
+    let xpriv = cosigner_master_xpriv(seed)
+        .derive_priv(&secp, &account_path)
+        .unwrap();
+    Xpub::from_priv(&secp, &xpriv).to_string()
+}
+
 fn create_token(root: &KeyPair, role: Role, expiration_date: Option<DateTime<Utc>>) -> String {
     let mut authority = biscuit!("");
     match role {
@@ -124,37 +172,119 @@ async fn check_response_passed_auth(res: Response) {
     }
 }
 
-pub(crate) fn unique_bytes() -> Vec<u8> {
-    COUNTER
-        .fetch_add(1, Ordering::SeqCst)
-        .to_be_bytes()
-        .to_vec()
+// keys of all the test cosigners at the given path, with a 3-of-4 multisig script locking to them
+fn multisig_keys(
+    key_path: &DerivationPath,
+) -> (
+    BTreeMap<PublicKey, (Fingerprint, DerivationPath)>,
+    ScriptBuf,
+) {
+    let secp = Secp256k1::new();
+    let mut bip32_derivation = BTreeMap::new();
+    for seed in 0..NUM_COSIGNERS {
+        let master_xpriv = cosigner_master_xpriv(seed);
+        let key = master_xpriv
+            .derive_priv(&secp, key_path)
+            .unwrap()
+            .private_key
+            .public_key(&secp);
+        bip32_derivation.insert(key, (master_xpriv.fingerprint(&secp), key_path.clone()));
+    }
+    // 3-of-4 multisig, matching the default test thresholds
+    let mut keys: Vec<_> = bip32_derivation.keys().map(|k| k.serialize()).collect();
+    keys.sort();
+    let mut builder = Builder::new().push_int(3);
+    for key in keys {
+        builder = builder.push_slice(key);
+    }
+    let witness_script = builder
+        .push_int(NUM_COSIGNERS as i64)
+        .push_opcode(OP_CHECKMULTISIG)
+        .into_script();
+    (bip32_derivation, witness_script)
 }
 
-async fn start_daemon(
-    app_dir: &str,
-    root_public_key: biscuit_auth::PublicKey,
-    cosigner_xpubs: Vec<(String, String)>,
-    threshold_colored: u8,
-    threshold_vanilla: u8,
-    rgb_lib_version: String,
-) -> SocketAddr {
+// PSBT spending an output locked to the keys of all the test cosigners, with a change output
+fn test_psbt() -> Psbt {
+    let account_path = DerivationPath::from_str(ACCOUNT_PATH).unwrap();
+    let key_path = account_path.extend([ChildNumber::from_normal_idx(0).unwrap(); 2]);
+    let (bip32_derivation, witness_script) = multisig_keys(&key_path);
+    let change_path = account_path.extend([
+        ChildNumber::from_normal_idx(1).unwrap(),
+        ChildNumber::from_normal_idx(0).unwrap(),
+    ]);
+    let (change_bip32_derivation, change_witness_script) = multisig_keys(&change_path);
+    let recipient_key = CompressedPublicKey(*bip32_derivation.keys().next().unwrap());
+    let unsigned_tx = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::new(Txid::from_byte_array([1; 32]), 0),
+            ..Default::default()
+        }],
+        output: vec![
+            TxOut {
+                value: Amount::from_sat(1000),
+                script_pubkey: ScriptBuf::new_p2wpkh(&recipient_key.wpubkey_hash()),
+            },
+            TxOut {
+                value: Amount::from_sat(500),
+                script_pubkey: ScriptBuf::new_p2wsh(&change_witness_script.wscript_hash()),
+            },
+        ],
+    };
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_tx).unwrap();
+    psbt.inputs[0].witness_utxo = Some(TxOut {
+        value: Amount::from_sat(2000),
+        script_pubkey: ScriptBuf::new_p2wsh(&witness_script.wscript_hash()),
+    });
+    psbt.inputs[0].witness_script = Some(witness_script);
+    psbt.inputs[0].bip32_derivation = bip32_derivation;
+    psbt.outputs[1].witness_script = Some(change_witness_script);
+    psbt.outputs[1].bip32_derivation = change_bip32_derivation;
+    psbt
+}
+
+// address paid by the first output of the test PSBT
+fn psbt_recipient_address() -> String {
+    Address::from_script(
+        &test_psbt().unsigned_tx.output[0].script_pubkey,
+        Network::Regtest,
+    )
+    .unwrap()
+    .to_string()
+}
+
+fn psbt_bytes() -> Vec<u8> {
+    test_psbt().serialize()
+}
+
+fn signed_psbt_bytes(cosigner_idx: i32) -> Vec<u8> {
+    let mut psbt = test_psbt();
+    psbt.sign(
+        &cosigner_master_xpriv(cosigner_idx as u8),
+        &Secp256k1::new(),
+    )
+    .unwrap();
+    psbt.serialize()
+}
+
+// PSBT whose key origins use the mainnet coin type
+fn mainnet_psbt_bytes() -> Vec<u8> {
+    let mut psbt = test_psbt();
+    for (_, path) in psbt.inputs[0].bip32_derivation.values_mut() {
+        let mut children: Vec<ChildNumber> = path.into_iter().cloned().collect();
+        children[1] = ChildNumber::from_hardened_idx(0).unwrap();
+        *path = DerivationPath::from(children);
+    }
+    psbt.serialize()
+}
+
+async fn start_daemon(app_params: AppParams) -> SocketAddr {
     let listener = TcpListener::bind("0.0.0.0:0").await.unwrap();
     let node_address = listener.local_addr().unwrap();
-    let _ = std::fs::remove_dir_all(app_dir);
-    std::fs::create_dir_all(app_dir).unwrap();
-    let app_params = AppParams {
-        app_dir: app_dir.into(),
-        daemon_listening_port: 3001,
-        root_public_key,
-        cosigner_xpubs: cosigner_xpubs
-            .iter()
-            .map(|(xpub, _)| xpub.clone())
-            .collect(),
-        threshold_colored,
-        threshold_vanilla,
-        rgb_lib_version,
-    };
+    let _ = std::fs::remove_dir_all(&app_params.app_dir);
+    std::fs::create_dir_all(&app_params.app_dir).unwrap();
     tokio::spawn(async move {
         let (router, app_state) = app(app_params).await.unwrap();
         axum::serve(listener, router)
@@ -166,10 +296,17 @@ async fn start_daemon(
 }
 
 async fn setup_daemon(app_dir: &str) -> TestContext {
+    setup_daemon_with_params(app_dir, |_| {}).await
+}
+
+async fn setup_daemon_with_params<F>(app_dir: &str, customize_params: F) -> TestContext
+where
+    F: FnOnce(&mut AppParams),
+{
     let root_keypair = KeyPair::new();
     let mut cosigner_xpubs = Vec::new();
-    for i in 0..4 {
-        let xpub = format!("xpub{i}");
+    for i in 0..NUM_COSIGNERS {
+        let xpub = cosigner_xpub(i);
         cosigner_xpubs.push((
             xpub.clone(),
             create_token(&root_keypair, Role::Cosigner(xpub), None),
@@ -177,15 +314,32 @@ async fn setup_daemon(app_dir: &str) -> TestContext {
     }
     let watch_only_token = create_token(&root_keypair, Role::WatchOnly, None);
     let rgb_lib_version = "0.3".to_string();
-    let node_address = start_daemon(
-        app_dir,
-        root_keypair.public(),
-        cosigner_xpubs.clone(),
-        3,
-        3,
-        rgb_lib_version.clone(),
-    )
-    .await;
+    let mut app_params = AppParams {
+        app_dir: app_dir.into(),
+        daemon_listening_port: 3001,
+        network: BitcoinNetwork::Regtest,
+        root_public_key: root_keypair.public(),
+        cosigner_xpubs: cosigner_xpubs
+            .iter()
+            .map(|(xpub, _)| xpub.clone())
+            .collect(),
+        cosigner_weights: vec![1; cosigner_xpubs.len()],
+        cosigner_roles: HashMap::new(),
+        threshold_colored: 3,
+        threshold_vanilla: 3,
+        thresholds: HashMap::new(),
+        auto_approved_ops: DEFAULT_AUTO_APPROVED_OPS.to_vec(),
+        rgb_lib_version: rgb_lib_version.clone(),
+        pending_timeout: None,
+        webhooks: vec![],
+        enforce_address_allowlist: false,
+        policies: vec![],
+        spending_limits: vec![],
+        approval_delays: HashMap::new(),
+        chain_source: None,
+    };
+    customize_params(&mut app_params);
+    let node_address = start_daemon(app_params).await;
     TestContext {
         node_address,
         watch_only_token,
@@ -195,6 +349,98 @@ async fn setup_daemon(app_dir: &str) -> TestContext {
     }
 }
 
+// local stand-in for a webhook endpoint, recording the requests it receives
+#[derive(Clone)]
+struct WebhookReceiver {
+    url: String,
+    requests: Arc<Mutex<Vec<(axum::http::HeaderMap, Bytes)>>>,
+    failures_left: Arc<AtomicU32>,
+}
+
+impl WebhookReceiver {
+    fn requests(&self) -> Vec<(axum::http::HeaderMap, Bytes)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn webhook_receiver_handler(
+    axum::extract::State(receiver): axum::extract::State<WebhookReceiver>,
+    headers: axum::http::HeaderMap,
+    body: Bytes,
+) -> axum::http::StatusCode {
+    let fail = receiver
+        .failures_left
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+        .is_ok();
+    if fail {
+        return axum::http::StatusCode::INTERNAL_SERVER_ERROR;
+    }
+    receiver.requests.lock().unwrap().push((headers, body));
+    axum::http::StatusCode::OK
+}
+
+async fn start_webhook_receiver(failures: u32) -> WebhookReceiver {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let receiver = WebhookReceiver {
+        url: format!("http://{}/webhook", listener.local_addr().unwrap()),
+        requests: Arc::new(Mutex::new(Vec::new())),
+        failures_left: Arc::new(AtomicU32::new(failures)),
+    };
+    let router = axum::Router::new()
+        .route("/webhook", axum::routing::post(webhook_receiver_handler))
+        .with_state(receiver.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    receiver
+}
+
+// local stand-in for an Esplora API, serving the status of the transactions it knows
+#[derive(Clone)]
+struct MockEsplora {
+    url: String,
+    // block height of each known transaction, None if unconfirmed
+    txs: Arc<Mutex<HashMap<String, Option<u32>>>>,
+}
+
+impl MockEsplora {
+    fn set_tx(&self, txid: &str, block_height: Option<u32>) {
+        self.txs
+            .lock()
+            .unwrap()
+            .insert(txid.to_string(), block_height);
+    }
+}
+
+async fn mock_esplora_tx_status_handler(
+    axum::extract::State(esplora): axum::extract::State<MockEsplora>,
+    axum::extract::Path(txid): axum::extract::Path<String>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    match esplora.txs.lock().unwrap().get(&txid) {
+        Some(block_height) => axum::Json(serde_json::json!({
+            "confirmed": block_height.is_some(),
+            "block_height": block_height,
+        }))
+        .into_response(),
+        None => axum::http::StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn start_mock_esplora() -> MockEsplora {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let esplora = MockEsplora {
+        url: format!("http://{}", listener.local_addr().unwrap()),
+        txs: Arc::new(Mutex::new(HashMap::new())),
+    };
+    let router = axum::Router::new()
+        .route(
+            "/tx/:txid/status",
+            axum::routing::get(mock_esplora_tx_status_handler),
+        )
+        .with_state(esplora.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    esplora
+}
+
 async fn setup_with_pending_operation(app_dir: &str) -> (TestContext, i32) {
     let ctx = setup_daemon(app_dir).await;
     let res = post_operation(&ctx, OperationType::SendRgb).await;
@@ -210,7 +456,7 @@ async fn setup_with_approved_operation(app_dir: &str) -> (TestContext, i32) {
         };
         let json_payload = serde_json::to_string(&req).unwrap();
         let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
-        let psbt_part = multipart::Part::bytes(b"psbt".to_vec())
+        let psbt_part = multipart::Part::bytes(signed_psbt_bytes(cosigner_idx))
             .mime_str(OCTET_STREAM)
             .unwrap();
         let form = multipart::Form::new()
@@ -247,6 +493,32 @@ async fn bump_address_indices(
     }
 }
 
+async fn cancel_operation(
+    ctx: &TestContext,
+    operation_idx: i32,
+    cosigner_idx: i32,
+) -> OperationResponse {
+    let req = CancelOperationRequest { operation_idx };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/canceloperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(cosigner_idx))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<OperationResponse>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(res) => res,
+        APIResponse::Error(error) => {
+            panic!("failed to cancel operation: {error:?}");
+        }
+    }
+}
+
 async fn get_current_address_indices(ctx: &TestContext) -> GetCurrentAddressIndicesResponse {
     let res = reqwest::Client::new()
         .get(format!(
@@ -318,7 +590,11 @@ async fn get_operation_by_idx(
     operation_idx: i32,
     cosigner_idx: Option<i32>,
 ) -> Option<OperationResponse> {
-    let req = GetOperationByIdxRequest { operation_idx };
+    let req = GetOperationByIdxRequest {
+        operation_idx,
+        wait: None,
+        status: None,
+    };
     let token = match cosigner_idx {
         Some(cosigner_idx) => ctx.get_cosigner_token(cosigner_idx),
         None => ctx.watch_only_token.clone(),
@@ -368,8 +644,73 @@ async fn info(ctx: &TestContext, cosigner_idx: Option<i32>) -> InfoResponse {
     }
 }
 
+async fn list_operations(
+    ctx: &TestContext,
+    req: &ListOperationsRequest,
+    cosigner_idx: Option<i32>,
+) -> ListOperationsResponse {
+    let token = match cosigner_idx {
+        Some(cosigner_idx) => ctx.get_cosigner_token(cosigner_idx),
+        None => ctx.watch_only_token.clone(),
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/listoperations", ctx.node_address))
+        .bearer_auth(token)
+        .json(req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<ListOperationsResponse>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(res) => res,
+        APIResponse::Error(error) => {
+            panic!("failed to list operations: {error:?}");
+        }
+    }
+}
+
+async fn list_webhook_deliveries(
+    ctx: &TestContext,
+    req: &ListWebhookDeliveriesRequest,
+) -> ListWebhookDeliveriesResponse {
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/listwebhookdeliveries", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<ListWebhookDeliveriesResponse>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(res) => res,
+        APIResponse::Error(error) => {
+            panic!("failed to list webhook deliveries: {error:?}");
+        }
+    }
+}
+
 async fn mark_operation_processed(ctx: &TestContext, operation_idx: i32, cosigner_idx: i32) {
-    let req = MarkOperationProcessedRequest { operation_idx };
+    mark_operation_processed_with_result(ctx, operation_idx, cosigner_idx, None).await;
+}
+
+async fn mark_operation_processed_with_result(
+    ctx: &TestContext,
+    operation_idx: i32,
+    cosigner_idx: i32,
+    result: Option<ProcessingResult>,
+) {
+    let req = MarkOperationProcessedRequest {
+        operation_idx,
+        result,
+    };
     let res = reqwest::Client::new()
         .post(format!(
             "http://{}/markoperationprocessed",
@@ -395,7 +736,7 @@ async fn mark_operation_processed(ctx: &TestContext, operation_idx: i32, cosigne
 
 async fn post_operation(ctx: &TestContext, operation_type: OperationType) -> PostOperationResponse {
     let operation_type_part = multipart::Part::bytes((operation_type as u8).to_le_bytes().to_vec());
-    let psbt_part = multipart::Part::bytes(b"psbt".to_vec());
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(0));
     let form = multipart::Form::new()
         .part("operation_type", operation_type_part)
         .part("file_psbt", psbt_part);
@@ -425,15 +766,196 @@ async fn post_operation_with_multipart_form(
     }
 }
 
-fn respond_to_operation_form(operation_idx: i32, ack: bool, with_psbt: bool) -> multipart::Form {
+async fn sync_operations(
+    ctx: &TestContext,
+    after_idx: i32,
+    cosigner_idx: Option<i32>,
+) -> SyncOperationsResponse {
+    let req = SyncOperationsRequest {
+        after_idx,
+        archive: false,
+    };
+    let token = match cosigner_idx {
+        Some(cosigner_idx) => ctx.get_cosigner_token(cosigner_idx),
+        None => ctx.watch_only_token.clone(),
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/syncoperations", ctx.node_address))
+        .bearer_auth(token)
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<SyncOperationsResponse>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(res) => res,
+        APIResponse::Error(error) => {
+            panic!("failed to sync operations: {error:?}");
+        }
+    }
+}
+
+fn update_allowlist_form(add: Vec<String>, remove: Vec<String>) -> multipart::Form {
+    let operation_type_part = multipart::Part::bytes(
+        (OperationType::UpdateAllowlist as u8)
+            .to_le_bytes()
+            .to_vec(),
+    );
+    let update = AllowlistUpdate { add, remove };
+    let json_payload = serde_json::to_string(&update).unwrap();
+    let update_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
+    multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("allowlist_update", update_part)
+}
+
+// update the allowlist through an approved operation, processed by all cosigners
+async fn update_allowlist(ctx: &TestContext, add: Vec<String>, remove: Vec<String>) -> i32 {
+    let form = update_allowlist_form(add, remove);
+    let operation_idx = post_operation_with_multipart_form(ctx, form, 0)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..ctx.num_cosigners() {
+        let form = respond_to_operation_form(operation_idx, true, None);
+        let res = respond_to_operation(ctx, form, cosigner_idx).await;
+        if res.status == OperationStatus::Approved {
+            break;
+        }
+    }
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(ctx, operation_idx, cosigner_idx).await;
+    }
+    operation_idx
+}
+
+async fn update_chain_status(
+    ctx: &TestContext,
+    req: &UpdateChainStatusRequest,
+    cosigner_idx: i32,
+) -> OperationResponse {
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/updatechainstatus", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(cosigner_idx))
+        .json(req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<OperationResponse>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(res) => res,
+        APIResponse::Error(error) => {
+            panic!("failed to update chain status: {error:?}");
+        }
+    }
+}
+
+async fn wait_for_operation(
+    ctx: &TestContext,
+    operation_idx: i32,
+    status: Option<OperationStatus>,
+    wait: u32,
+) -> Option<OperationResponse> {
+    let req = GetOperationByIdxRequest {
+        operation_idx,
+        wait: Some(wait),
+        status,
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/getoperationbyidx", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<Option<OperationResponse>>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(res) => res,
+        APIResponse::Error(error) => {
+            panic!("failed to wait for operation: {error:?}");
+        }
+    }
+}
+
+async fn veto_operation(
+    ctx: &TestContext,
+    operation_idx: i32,
+    cosigner_idx: i32,
+) -> OperationResponse {
+    let req = VetoOperationRequest { operation_idx };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/vetooperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(cosigner_idx))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<OperationResponse>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(res) => res,
+        APIResponse::Error(error) => {
+            panic!("failed to veto operation: {error:?}");
+        }
+    }
+}
+
+async fn read_events(res: &mut Response, count: usize) -> Vec<EventResponse> {
+    let mut events = Vec::new();
+    let mut buffer = String::new();
+    tokio::time::timeout(EVENTS_TIMEOUT, async {
+        while events.len() < count {
+            let chunk = res.chunk().await.unwrap().expect("event stream closed");
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+            while let Some(end) = buffer.find("\n\n") {
+                let message: String = buffer.drain(..end + 2).collect();
+                let mut id = None;
+                let mut data = None;
+                for line in message.lines() {
+                    if let Some(value) = line.strip_prefix("id:") {
+                        id = Some(value.trim().parse::<i32>().unwrap());
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data = Some(value.trim().to_string());
+                    }
+                }
+                // skip keep-alive messages
+                let Some(data) = data else { continue };
+                let event: EventResponse = serde_json::from_str(&data).unwrap();
+                assert_eq!(id, Some(event.event_idx));
+                events.push(event);
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for events");
+    events
+}
+
+fn respond_to_operation_form(
+    operation_idx: i32,
+    ack: bool,
+    psbt: Option<Vec<u8>>,
+) -> multipart::Form {
     let req = RespondToOperationRequest { operation_idx, ack };
     let json_payload = serde_json::to_string(&req).unwrap();
     let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
     let mut form = multipart::Form::new().part("request", json_part);
-    if with_psbt {
-        let psbt_part = multipart::Part::bytes(unique_bytes())
-            .mime_str(OCTET_STREAM)
-            .unwrap();
+    if let Some(psbt) = psbt {
+        let psbt_part = multipart::Part::bytes(psbt).mime_str(OCTET_STREAM).unwrap();
         form = form.part("file_psbt", psbt_part);
     }
     form
@@ -462,6 +984,46 @@ async fn respond_to_operation(
     }
 }
 
+async fn revoke_token(ctx: &TestContext, token: String, cosigner_idx: i32) {
+    let req = RevokeTokenRequest { token };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/revoketoken", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(cosigner_idx))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res)
+        .await
+        .json::<APIResponse<EmptyResponse>>()
+        .await
+        .unwrap();
+    match res {
+        APIResponse::Success(_) => {}
+        APIResponse::Error(error) => {
+            panic!("failed to revoke token: {error:?}");
+        }
+    }
+}
+
+async fn subscribe_events(
+    ctx: &TestContext,
+    last_event_idx: Option<i32>,
+    cosigner_idx: Option<i32>,
+) -> Response {
+    let token = match cosigner_idx {
+        Some(cosigner_idx) => ctx.get_cosigner_token(cosigner_idx),
+        None => ctx.watch_only_token.clone(),
+    };
+    let mut req = reqwest::Client::new()
+        .get(format!("http://{}/events", ctx.node_address))
+        .bearer_auth(token);
+    if let Some(last_event_idx) = last_event_idx {
+        req = req.header("Last-Event-ID", last_event_idx.to_string());
+    }
+    check_response_is_ok(req.send().await.unwrap()).await
+}
+
 // common test checks
 
 #[derive(Clone, Debug)]
@@ -820,12 +1382,29 @@ async fn multipart_form_checks(ctx: &TestContext, multipart_form_checks: Multipa
 
 // test modules
 
+mod address_allowlist;
+mod approval_delay;
+mod auto_approved_ops;
 mod bump_address_indices;
+mod cancel_operation;
+mod cosigner_roles;
+mod cosigner_weights;
+mod events;
 mod get_current_address_indices;
 mod get_file;
 mod get_last_processed_op_idx;
 mod get_operation_by_idx;
 mod info;
+mod list_operations;
+mod list_webhook_deliveries;
 mod mark_operation_processed;
+mod operation_thresholds;
+mod pending_timeout;
 mod post_operation;
 mod respond_to_operation;
+mod response_revision;
+mod revoke_token;
+mod spending_limits;
+mod sync_operations;
+mod update_chain_status;
+mod veto_operation;
@@ -0,0 +1,45 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/operation_thresholds/";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.threshold_vanilla = 2;
+        params.threshold_colored = 3;
+        params.thresholds =
+            HashMap::from([(OperationType::SendBtc, 4), (OperationType::Inflation, 2)]);
+    })
+    .await;
+
+    // operation types with a threshold use it, the others keep the vanilla or colored one
+    let test_cases = [
+        (OperationType::SendBtc, 4),
+        (OperationType::CreateUtxos, 2),
+        (OperationType::Inflation, 2),
+        (OperationType::SendRgb, 3),
+    ];
+    for (operation_type, expected_threshold) in test_cases {
+        let operation_idx = post_operation(&ctx, operation_type).await.operation_idx;
+        let res = get_operation_by_idx(&ctx, operation_idx, None)
+            .await
+            .unwrap();
+        assert_eq!(res.threshold, Some(expected_threshold));
+        cancel_operation(&ctx, operation_idx, 0).await;
+        for cosigner_idx in 0..ctx.num_cosigners() {
+            mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+        }
+    }
+
+    // the operation type threshold is enforced
+    let operation_idx = post_operation(&ctx, OperationType::Inflation)
+        .await
+        .operation_idx;
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Approved);
+}
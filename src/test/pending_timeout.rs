@@ -0,0 +1,85 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/pending_timeout/";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let pending_timeout = 2;
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.pending_timeout = Some(pending_timeout);
+    })
+    .await;
+
+    // pending operation reports its expiry time
+    let operation_idx = post_operation(&ctx, OperationType::SendRgb)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert_eq!(
+        res.expires_at,
+        Some(res.created_at + pending_timeout as i64)
+    );
+
+    // responses don't extend the deadline
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+
+    // operation expires once the deadline has passed
+    tokio::time::sleep(Duration::from_secs(pending_timeout * 2 + 1)).await;
+    let res = get_operation_by_idx(&ctx, operation_idx, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Expired);
+    assert!(res.expires_at.is_none());
+
+    // expired operation cannot be responded to
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(2)));
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/respondtooperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(2))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "operation is not pending",
+        "CannotRespondToOperation",
+    )
+    .await;
+
+    // expired operation is skipped by all cosigners and a new one can be posted
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+    let res = post_operation(&ctx, OperationType::SendRgb).await;
+    assert_eq!(res.operation_idx, operation_idx + 1);
+
+    // auto-approved operations don't expire
+    let app_dir = format!("{TEST_DIR_BASE}success_auto_approved");
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.pending_timeout = Some(1);
+    })
+    .await;
+    let operation_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert!(res.expires_at.is_none());
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Approved);
+}
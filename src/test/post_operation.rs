@@ -14,7 +14,7 @@ async fn success() {
     let ctx = setup_daemon(&app_dir).await;
     let operation_type_part =
         multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
-    let psbt_part = multipart::Part::bytes(b"psbt".to_vec());
+    let psbt_part = multipart::Part::bytes(psbt_bytes());
     let consignment_part_1 = multipart::Part::bytes(b"consignment_data".to_vec());
     let consignment_part_2 = multipart::Part::bytes(b"consignment_data".to_vec());
     let form = multipart::Form::new()
@@ -32,7 +32,7 @@ async fn success() {
         .unwrap();
     assert_eq!(res.operation_idx, operation_idx);
     assert_eq!(res.operation_type, OperationType::SendRgb);
-    assert_eq!(res.initiator_xpub, "xpub0");
+    assert_eq!(res.initiator_xpub, ctx.cosigners[0].0);
     assert!(res.created_at >= before_post && res.created_at <= after_post);
     assert_eq!(res.status, OperationStatus::Pending);
     assert!(!res.files.is_empty());
@@ -57,7 +57,7 @@ async fn success() {
     }
     assert_eq!(res.my_response, Some(true));
     assert_eq!(res.acked_by.len(), 1);
-    assert!(res.acked_by.contains("xpub0"));
+    assert!(res.acked_by.contains(&ctx.cosigners[0].0));
     assert_eq!(res.nacked_by.len(), 0);
     assert_eq!(res.threshold, Some(3));
     assert!(res.processed_at.is_none());
@@ -80,10 +80,11 @@ async fn success() {
         .unwrap();
     assert_eq!(res.operation_idx, operation_idx);
     assert_eq!(res.operation_type, OperationType::Issuance);
-    assert_eq!(res.initiator_xpub, "xpub0");
+    assert_eq!(res.initiator_xpub, ctx.cosigners[0].0);
     assert_eq!(res.status, OperationStatus::Approved);
     assert_eq!(res.files.len(), 1);
     assert_eq!(res.files[0].r#type, FileType::Consignment);
+    assert!(res.psbt_summary.is_none());
     assert!(res.threshold.is_none());
     assert_eq!(res.my_response, Some(true));
 
@@ -133,7 +134,12 @@ async fn success() {
         let operation_type_part = multipart::Part::bytes((op_type as u8).to_le_bytes().to_vec());
         let mut form = multipart::Form::new().part("operation_type", operation_type_part);
         for file_type in &file_types {
-            let file_part = multipart::Part::bytes(format!("{}_content", file_type).into_bytes());
+            let file_part = if *file_type == "file_psbt" {
+                // PSBTs can also be base64-encoded
+                multipart::Part::text(test_psbt().to_string())
+            } else {
+                multipart::Part::bytes(format!("{}_content", file_type).into_bytes())
+            };
             form = form.part(*file_type, file_part);
         }
         let operation_idx = post_operation_with_multipart_form(&ctx, form, 0)
@@ -171,6 +177,71 @@ async fn success() {
         );
         assert_eq!(res.threshold, if is_auto_approved { None } else { Some(3) });
     }
+
+    // policies raise the threshold of the operations meeting their conditions
+    let test_cases = [
+        (vec![], OperationType::SendBtc, Some(4)),
+        (
+            vec![psbt_recipient_address()],
+            OperationType::SendBtc,
+            Some(3),
+        ),
+        (vec![], OperationType::SendRgb, Some(3)),
+        (vec![], OperationType::Issuance, None),
+    ];
+    for (idx, (address_allowlist, op_type, threshold)) in test_cases.into_iter().enumerate() {
+        let app_dir = format!("{TEST_DIR_BASE}policies_{idx}");
+        let ctx = setup_daemon_with_params(&app_dir, |params| {
+            params.policies = vec![PolicyConfig {
+                operation_types: vec![OperationType::SendBtc],
+                non_allowlisted_outputs: true,
+                threshold: Some(4),
+                ..Default::default()
+            }];
+        })
+        .await;
+        if !address_allowlist.is_empty() {
+            update_allowlist(&ctx, address_allowlist, vec![]).await;
+        }
+        let operation_idx = post_operation(&ctx, op_type).await.operation_idx;
+        let res = get_operation_by_idx(&ctx, operation_idx, None)
+            .await
+            .unwrap();
+        assert_eq!(res.threshold, threshold);
+    }
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn approved_without_psbt() {
+    let app_dir = format!("{TEST_DIR_BASE}approved_without_psbt");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.threshold_vanilla = 1;
+    })
+    .await;
+
+    // an operation approved without any PSBT has nothing to combine
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendBtc as u8).to_le_bytes().to_vec());
+    let consignment_part = multipart::Part::bytes(vec![1, 2, 3]);
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_consignment", consignment_part);
+    let operation_idx = post_operation_with_multipart_form(&ctx, form, 0)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Approved);
+    assert_eq!(res.files.len(), 1);
+    assert_eq!(res.files[0].r#type, FileType::Consignment);
+    assert_eq!(
+        res.files[0].posted_by_xpub,
+        Some(ctx.cosigners[0].0.clone())
+    );
 }
 
 #[serial_test::serial]
@@ -249,8 +320,8 @@ async fn fail() {
     // more than one PSBT provided
     let operation_type_part =
         multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
-    let psbt_part_1 = multipart::Part::bytes(b"psbt".to_vec());
-    let psbt_part_2 = multipart::Part::bytes(b"psbt".to_vec());
+    let psbt_part_1 = multipart::Part::bytes(psbt_bytes());
+    let psbt_part_2 = multipart::Part::bytes(psbt_bytes());
     let form = multipart::Form::new()
         .part("operation_type", operation_type_part)
         .part("file_psbt", psbt_part_1)
@@ -290,7 +361,7 @@ async fn fail() {
     .await;
 
     // operation type not provided
-    let psbt_part = multipart::Part::bytes(b"psbt".to_vec());
+    let psbt_part = multipart::Part::bytes(psbt_bytes());
     let form = multipart::Form::new().part("file_psbt", psbt_part);
     let res = reqwest::Client::new()
         .post(format!("http://{}/{}", ctx.node_address, PATH))
@@ -307,11 +378,158 @@ async fn fail() {
     )
     .await;
 
+    // invalid PSBT
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(b"psbt".to_vec());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "Invalid PSBT",
+        "InvalidPsbt",
+    )
+    .await;
+
+    // PSBT without outputs
+    let mut psbt = test_psbt();
+    psbt.unsigned_tx.output.clear();
+    psbt.outputs.clear();
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(psbt.serialize());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "transaction has no outputs",
+        "InvalidPsbt",
+    )
+    .await;
+
+    // PSBT outputs exceeding the maximum amount of bitcoin
+    let mut psbt = test_psbt();
+    psbt.unsigned_tx.output[0].value = Amount::MAX;
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(psbt.serialize());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "transaction outputs exceed the maximum amount of bitcoin",
+        "InvalidPsbt",
+    )
+    .await;
+
+    // PSBT derivation paths from another network
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(mainnet_psbt_bytes());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "does not belong to the Regtest network",
+        "NetworkMismatch",
+    )
+    .await;
+
+    // PSBT xPub from another network
+    let mut psbt = test_psbt();
+    let master_xpriv = Xpriv::new_master(NetworkKind::Main, &[0; 32]).unwrap();
+    let secp = Secp256k1::new();
+    psbt.xpub.insert(
+        Xpub::from_priv(&secp, &master_xpriv),
+        (master_xpriv.fingerprint(&secp), DerivationPath::master()),
+    );
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(psbt.serialize());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "does not belong to the Regtest network",
+        "NetworkMismatch",
+    )
+    .await;
+
+    // PSBT without xPubs nor derivation paths to check the network against
+    let mut psbt = test_psbt();
+    psbt.inputs[0].bip32_derivation.clear();
+    psbt.outputs[1].bip32_derivation.clear();
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(psbt.serialize());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "PSBT has no xPubs or derivation paths to check its network against",
+        "NetworkMismatch",
+    )
+    .await;
+
     // cannot post operation while there's already a pending one
     // - first operation succeeds
     let operation_type_part =
         multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
-    let psbt_part = multipart::Part::bytes(b"psbt".to_vec());
+    let psbt_part = multipart::Part::bytes(psbt_bytes());
     let form = multipart::Form::new()
         .part("operation_type", operation_type_part)
         .part("file_psbt", psbt_part);
@@ -326,7 +544,7 @@ async fn fail() {
     // - second operation while the first is still pending fails
     let operation_type_part =
         multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
-    let psbt_part = multipart::Part::bytes(b"psbt".to_vec());
+    let psbt_part = multipart::Part::bytes(psbt_bytes());
     let form = multipart::Form::new()
         .part("operation_type", operation_type_part)
         .part("file_psbt", psbt_part);
@@ -350,7 +568,10 @@ async fn fail() {
     let (ctx, operation_idx) = setup_with_approved_operation(&app_dir_2).await;
     // - mark operation as processed by other cosigners but NOT by the initiator (cosigner 0)
     for cosigner_idx in 1..=3 {
-        let req = MarkOperationProcessedRequest { operation_idx };
+        let req = MarkOperationProcessedRequest {
+            operation_idx,
+            result: None,
+        };
         let res = reqwest::Client::new()
             .post(format!(
                 "http://{}/markoperationprocessed",
@@ -366,7 +587,7 @@ async fn fail() {
     // - try to post a new operation by the same initiator (cosigner 0) who hasn't processed the previous one
     let operation_type_part =
         multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
-    let psbt_part = multipart::Part::bytes(b"psbt".to_vec());
+    let psbt_part = multipart::Part::bytes(psbt_bytes());
     let form = multipart::Form::new()
         .part("operation_type", operation_type_part)
         .part("file_psbt", psbt_part);
@@ -384,4 +605,42 @@ async fn fail() {
         "CannotPostNewOperation",
     )
     .await;
+
+    // operation rejected by a policy
+    let app_dir = format!("{TEST_DIR_BASE}fail_policy");
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.policies = vec![
+            PolicyConfig {
+                sent_amount_above: Some(500),
+                threshold: Some(4),
+                ..Default::default()
+            },
+            PolicyConfig {
+                fee_rate_above: Some(2.0),
+                reject: true,
+                ..Default::default()
+            },
+        ];
+    })
+    .await;
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendBtc as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(0));
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Operation rejected by policy: operation matches policy #2",
+        "RejectedByPolicy",
+    )
+    .await;
 }
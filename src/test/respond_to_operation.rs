@@ -15,13 +15,13 @@ async fn success() {
     let num_cosigners = ctx.num_cosigners();
 
     // operation discarded
-    let form = respond_to_operation_form(operation_idx, true, true);
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
     let res = respond_to_operation(&ctx, form, 1).await;
     assert_eq!(res.operation_idx, operation_idx);
     assert_eq!(res.status, OperationStatus::Pending);
     assert_eq!(res.acked_by.len(), 2);
-    assert!(res.acked_by.contains("xpub0"));
-    assert!(res.acked_by.contains("xpub1"));
+    assert!(res.acked_by.contains(&ctx.cosigners[0].0));
+    assert!(res.acked_by.contains(&ctx.cosigners[1].0));
     assert_eq!(res.nacked_by.len(), 0);
     assert_eq!(res.my_response, Some(true));
     let psbt_files: Vec<_> = res
@@ -29,7 +29,7 @@ async fn success() {
         .iter()
         .filter(|f| f.r#type == FileType::Psbt)
         .collect();
-    assert!(psbt_files.len() >= 2);
+    assert_eq!(psbt_files.len(), 2);
     let files_dir = Path::new(&app_dir).join(FILES_DIR);
     for file in &res.files {
         let file_path = files_dir.join(&file.file_id);
@@ -37,14 +37,15 @@ async fn success() {
         let metadata = tokio::fs::metadata(&file_path).await.unwrap();
         assert_eq!(metadata.len(), file.size_bytes);
     }
-    let form = respond_to_operation_form(operation_idx, false, false);
+    let form = respond_to_operation_form(operation_idx, false, None);
     let res = respond_to_operation(&ctx, form, 2).await;
     assert_eq!(res.operation_idx, operation_idx);
     assert_eq!(res.status, OperationStatus::Pending);
-    let form = respond_to_operation_form(operation_idx, false, false);
+    let form = respond_to_operation_form(operation_idx, false, None);
     let res = respond_to_operation(&ctx, form, 3).await;
     assert_eq!(res.operation_idx, operation_idx);
     assert_eq!(res.status, OperationStatus::Discarded);
+    assert!(res.files.iter().all(|f| f.r#type != FileType::CombinedPsbt));
     for cosigner_idx in 0..num_cosigners {
         mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
     }
@@ -53,19 +54,36 @@ async fn success() {
     let operation_idx = post_operation(&ctx, OperationType::SendRgb)
         .await
         .operation_idx;
-    let form = respond_to_operation_form(operation_idx, true, true);
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
     let res = respond_to_operation(&ctx, form, 1).await;
     assert_eq!(res.operation_idx, operation_idx);
     assert_eq!(res.status, OperationStatus::Pending);
     assert_eq!(res.acked_by.len(), 2);
-    let form = respond_to_operation_form(operation_idx, true, true);
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(2)));
     let res = respond_to_operation(&ctx, form, 2).await;
     assert_eq!(res.operation_idx, operation_idx);
     assert_eq!(res.status, OperationStatus::Approved);
     assert_eq!(res.acked_by.len(), 3);
-    assert!(res.acked_by.contains("xpub0"));
-    assert!(res.acked_by.contains("xpub1"));
-    assert!(res.acked_by.contains("xpub2"));
+    assert!(res.acked_by.contains(&ctx.cosigners[0].0));
+    assert!(res.acked_by.contains(&ctx.cosigners[1].0));
+    assert!(res.acked_by.contains(&ctx.cosigners[2].0));
+
+    // ACK PSBTs are combined and finalized
+    let combined_files: Vec<_> = res
+        .files
+        .iter()
+        .filter(|f| f.r#type == FileType::CombinedPsbt)
+        .collect();
+    assert_eq!(combined_files.len(), 1);
+    assert!(combined_files[0].posted_by_xpub.is_none());
+    let res = get_file(&ctx, combined_files[0].file_id.clone(), None).await;
+    let psbt = Psbt::deserialize(&res.bytes().await.unwrap()).unwrap();
+    assert!(psbt.inputs[0].partial_sigs.is_empty());
+    let witness = psbt.inputs[0].final_script_witness.clone().unwrap();
+    // empty element for CHECKMULTISIG, 3 signatures and the witness script
+    assert_eq!(witness.len(), 5);
+    let tx = psbt.extract_tx().unwrap();
+    assert_eq!(tx.compute_txid(), test_psbt().unsigned_tx.compute_txid());
 }
 
 #[serial_test::serial]
@@ -128,10 +146,10 @@ async fn fail() {
     };
     let json_payload = serde_json::to_string(&req).unwrap();
     let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
-    let psbt_part_1 = multipart::Part::bytes(unique_bytes())
+    let psbt_part_1 = multipart::Part::bytes(signed_psbt_bytes(1))
         .mime_str(OCTET_STREAM)
         .unwrap();
-    let psbt_part_2 = multipart::Part::bytes(unique_bytes())
+    let psbt_part_2 = multipart::Part::bytes(signed_psbt_bytes(1))
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
@@ -154,7 +172,7 @@ async fn fail() {
     .await;
 
     // missing request body
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(2))
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new().part("file_psbt", psbt_part);
@@ -192,14 +210,10 @@ async fn fail() {
     )
     .await;
 
-    // respond to your own operation
-    let req = RespondToOperationRequest {
-        operation_idx,
-        ack: true,
-    };
+    // invalid PSBT
     let json_payload = serde_json::to_string(&req).unwrap();
     let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    let psbt_part = multipart::Part::bytes(b"psbt".to_vec())
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
@@ -207,45 +221,97 @@ async fn fail() {
         .part("file_psbt", psbt_part);
     let res = reqwest::Client::new()
         .post(format!("http://{}/{}", ctx.node_address, PATH))
-        .bearer_auth(ctx.get_cosigner_token(0))
+        .bearer_auth(ctx.get_cosigner_token(1))
         .multipart(form)
         .send()
         .await
         .unwrap();
     check_response_is_nok(
         res,
-        reqwest::StatusCode::FORBIDDEN,
-        "cannot respond to your own operation",
-        "CannotRespondToOperation",
+        reqwest::StatusCode::BAD_REQUEST,
+        "Invalid PSBT",
+        "InvalidPsbt",
     )
     .await;
 
-    // already responded to this operation
-    let req = RespondToOperationRequest {
-        operation_idx,
-        ack: true,
-    };
+    // PSBT from another network
     let json_payload = serde_json::to_string(&req).unwrap();
-    let json_part = multipart::Part::text(json_payload.clone())
-        .mime_str(JSON)
+    let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
+    let psbt_part = multipart::Part::bytes(mainnet_psbt_bytes())
+        .mime_str(OCTET_STREAM)
         .unwrap();
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    let form = multipart::Form::new()
+        .part("request", json_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "does not belong to the Regtest network",
+        "NetworkMismatch",
+    )
+    .await;
+
+    // PSBT spending a different transaction
+    let mut psbt = test_psbt();
+    psbt.unsigned_tx.output[0].value = Amount::from_sat(2000);
+    let json_payload = serde_json::to_string(&req).unwrap();
+    let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
+    let psbt_part = multipart::Part::bytes(psbt.serialize())
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
         .part("request", json_part)
         .part("file_psbt", psbt_part);
-    reqwest::Client::new()
+    let res = reqwest::Client::new()
         .post(format!("http://{}/{}", ctx.node_address, PATH))
         .bearer_auth(ctx.get_cosigner_token(1))
         .multipart(form)
         .send()
         .await
         .unwrap();
-    let json_part = multipart::Part::text(json_payload.clone())
-        .mime_str(JSON)
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "transaction differs from the first uploaded one",
+        "InvalidPsbt",
+    )
+    .await;
+
+    // ACK PSBT without signatures
+    let json_payload = serde_json::to_string(&req).unwrap();
+    let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
+    let psbt_part = multipart::Part::bytes(psbt_bytes())
+        .mime_str(OCTET_STREAM)
+        .unwrap();
+    let form = multipart::Form::new()
+        .part("request", json_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .multipart(form)
+        .send()
+        .await
         .unwrap();
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "no signature from the responding cosigner",
+        "InvalidPsbt",
+    )
+    .await;
+
+    // ACK PSBT signed by another cosigner
+    let json_payload = serde_json::to_string(&req).unwrap();
+    let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(2))
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
@@ -258,19 +324,85 @@ async fn fail() {
         .send()
         .await
         .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "input 0 has a signature not belonging to the responding cosigner",
+        "InvalidPsbt",
+    )
+    .await;
+
+    // ACK PSBT with a signature over a different transaction
+    let mut other_psbt = test_psbt();
+    other_psbt.unsigned_tx.output[0].value = Amount::from_sat(2000);
+    other_psbt
+        .sign(&cosigner_master_xpriv(1), &Secp256k1::new())
+        .unwrap();
+    let mut psbt = test_psbt();
+    psbt.inputs[0].partial_sigs = other_psbt.inputs[0].partial_sigs.clone();
+    let json_payload = serde_json::to_string(&req).unwrap();
+    let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
+    let psbt_part = multipart::Part::bytes(psbt.serialize())
+        .mime_str(OCTET_STREAM)
+        .unwrap();
+    let form = multipart::Form::new()
+        .part("request", json_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "input 0 has an invalid signature",
+        "InvalidPsbt",
+    )
+    .await;
+
+    // respond to your own operation
+    let req = RespondToOperationRequest {
+        operation_idx,
+        ack: true,
+    };
+    let json_payload = serde_json::to_string(&req).unwrap();
+    let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(0))
+        .mime_str(OCTET_STREAM)
+        .unwrap();
+    let form = multipart::Form::new()
+        .part("request", json_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
     check_response_is_nok(
         res,
         reqwest::StatusCode::FORBIDDEN,
-        "already responded to this operation",
+        "cannot respond to your own operation",
         "CannotRespondToOperation",
     )
     .await;
 
     // respond to operation that is not pending
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    respond_to_operation(&ctx, form, 1).await;
+    let req = RespondToOperationRequest {
+        operation_idx,
+        ack: true,
+    };
+    let json_payload = serde_json::to_string(&req).unwrap();
     let json_part = multipart::Part::text(json_payload.clone())
         .mime_str(JSON)
         .unwrap();
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(2))
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
@@ -284,7 +416,7 @@ async fn fail() {
         .await
         .unwrap();
     let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(3))
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
@@ -311,6 +443,7 @@ async fn fail() {
     for cosigner_idx in [0, 1] {
         let req = MarkOperationProcessedRequest {
             operation_idx: operation_idx_1,
+            result: None,
         };
         let res = reqwest::Client::new()
             .post(format!(
@@ -326,7 +459,7 @@ async fn fail() {
     }
     let operation_type_part =
         multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
-    let psbt_part = multipart::Part::bytes(b"psbt2".to_vec());
+    let psbt_part = multipart::Part::bytes(psbt_bytes());
     let form = multipart::Form::new()
         .part("operation_type", operation_type_part)
         .part("file_psbt", psbt_part);
@@ -355,7 +488,7 @@ async fn fail() {
     };
     let json_payload = serde_json::to_string(&req).unwrap();
     let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(2))
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
@@ -377,6 +510,7 @@ async fn fail() {
     .await;
     let req = MarkOperationProcessedRequest {
         operation_idx: operation_idx_1,
+        result: None,
     };
     let res = reqwest::Client::new()
         .post(format!(
@@ -395,7 +529,7 @@ async fn fail() {
     };
     let json_payload = serde_json::to_string(&req).unwrap();
     let json_part = multipart::Part::text(json_payload).mime_str(JSON).unwrap();
-    let psbt_part = multipart::Part::bytes(unique_bytes())
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(2))
         .mime_str(OCTET_STREAM)
         .unwrap();
     let form = multipart::Form::new()
@@ -410,3 +544,66 @@ async fn fail() {
         .unwrap();
     let _ = check_response_is_ok(res).await;
 }
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn initiator_without_psbt() {
+    let app_dir = format!("{TEST_DIR_BASE}initiator_without_psbt");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    // initiator posts no PSBT
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_consignment", multipart::Part::bytes(vec![1, 2, 3]));
+    let operation_idx = post_operation_with_multipart_form(&ctx, form, 0)
+        .await
+        .operation_idx;
+
+    // first responder's PSBT sets the transaction
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+
+    // later responders must spend the same transaction
+    let mut psbt = test_psbt();
+    psbt.unsigned_tx.output[0].value = Amount::from_sat(2000);
+    let form = respond_to_operation_form(operation_idx, false, Some(psbt.serialize()));
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(2))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "transaction differs from the first uploaded one",
+        "InvalidPsbt",
+    )
+    .await;
+    let form = respond_to_operation_form(operation_idx, false, Some(psbt_bytes()));
+    let res = respond_to_operation(&ctx, form, 2).await;
+    assert_eq!(res.my_response, Some(false));
+
+    // a revised response is also checked against the first uploaded PSBT
+    let form = respond_to_operation_form(operation_idx, false, Some(psbt.serialize()));
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "transaction differs from the first uploaded one",
+        "InvalidPsbt",
+    )
+    .await;
+}
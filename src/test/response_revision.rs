@@ -0,0 +1,129 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/response_revision/";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let (ctx, operation_idx) = setup_with_pending_operation(&app_dir).await;
+
+    // initiator's ACK is the first response
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.response_history.len(), 1);
+    assert_eq!(res.response_history[0].cosigner_xpub, ctx.cosigners[0].0);
+    assert!(res.response_history[0].ack);
+
+    // ACK revised into a NACK
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert_eq!(res.acked_weight, 2);
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert_eq!(res.my_response, Some(false));
+    assert_eq!(res.acked_by, HashSet::from([ctx.cosigners[0].0.clone()]));
+    assert_eq!(res.nacked_by, HashSet::from([ctx.cosigners[1].0.clone()]));
+    assert_eq!(res.acked_weight, 1);
+    assert_eq!(res.nacked_weight, 1);
+    // the superseded PSBT is no longer listed
+    let psbt_posters: Vec<_> = res
+        .files
+        .iter()
+        .filter(|f| f.r#type == FileType::Psbt)
+        .map(|f| f.posted_by_xpub.clone())
+        .collect();
+    assert_eq!(psbt_posters, vec![Some(ctx.cosigners[0].0.clone())]);
+
+    // NACK revised back into an ACK, the status is recomputed on each revision
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    assert_eq!(res.nacked_weight, 0);
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(2)));
+    let res = respond_to_operation(&ctx, form, 2).await;
+    assert_eq!(res.status, OperationStatus::Approved);
+
+    // the full history is kept, oldest first
+    let history: Vec<_> = res
+        .response_history
+        .iter()
+        .map(|r| (r.cosigner_xpub.clone(), r.ack))
+        .collect();
+    assert_eq!(
+        history,
+        vec![
+            (ctx.cosigners[0].0.clone(), true),
+            (ctx.cosigners[1].0.clone(), true),
+            (ctx.cosigners[1].0.clone(), false),
+            (ctx.cosigners[1].0.clone(), true),
+            (ctx.cosigners[2].0.clone(), true),
+        ]
+    );
+    assert!(
+        res.response_history
+            .windows(2)
+            .all(|w| w[0].responded_at <= w[1].responded_at)
+    );
+
+    // only the latest PSBTs are listed and combined
+    assert_eq!(
+        res.files
+            .iter()
+            .filter(|f| f.r#type == FileType::Psbt)
+            .count(),
+        3
+    );
+    let combined_file = res
+        .files
+        .iter()
+        .find(|f| f.r#type == FileType::CombinedPsbt)
+        .unwrap();
+    let res = get_file(&ctx, combined_file.file_id.clone(), None).await;
+    let psbt = Psbt::deserialize(&res.bytes().await.unwrap()).unwrap();
+    // empty element for CHECKMULTISIG, 3 signatures and the witness script
+    assert_eq!(
+        psbt.inputs[0].final_script_witness.clone().unwrap().len(),
+        5
+    );
+
+    // responses cannot be revised once the operation is finalized
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/respondtooperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "operation is not pending",
+        "CannotRespondToOperation",
+    )
+    .await;
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // a revised NACK can discard the operation
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = respond_to_operation(&ctx, form, 2).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    let form = respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(1)));
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Pending);
+    let form = respond_to_operation_form(operation_idx, false, None);
+    let res = respond_to_operation(&ctx, form, 1).await;
+    assert_eq!(res.status, OperationStatus::Discarded);
+    assert_eq!(res.response_history.len(), 4);
+}
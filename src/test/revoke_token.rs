@@ -0,0 +1,157 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/revoke_token/";
+
+const PATH: &str = "revoketoken";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let mut ctx = setup_daemon(&app_dir).await;
+
+    // a cosigner can revoke the token it's using
+    let revoked_token = ctx.get_cosigner_token(1);
+    info(&ctx, Some(1)).await;
+    revoke_token(&ctx, revoked_token.clone(), 1).await;
+    let res = reqwest::Client::new()
+        .get(format!("http://{}/info", ctx.node_address))
+        .bearer_auth(&revoked_token)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::UNAUTHORIZED,
+        "Missing or invalid credentials",
+        "Unauthorized",
+    )
+    .await;
+
+    // a new token for the same cosigner is still accepted
+    let xpub = ctx.cosigners[1].0.clone();
+    ctx.cosigners[1].1 = create_token(&ctx.root_keypair, Role::Cosigner(xpub), None);
+    info(&ctx, Some(1)).await;
+
+    // other cosigners are not affected
+    info(&ctx, Some(0)).await;
+    info(&ctx, Some(2)).await;
+
+    // a cosigner can revoke another one of its tokens, with surrounding whitespace
+    let xpub = ctx.cosigners[2].0.clone();
+    let other_token = create_token(&ctx.root_keypair, Role::Cosigner(xpub), None);
+    revoke_token(&ctx, format!(" {other_token}\n"), 2).await;
+    let res = reqwest::Client::new()
+        .get(format!("http://{}/info", ctx.node_address))
+        .bearer_auth(&other_token)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::UNAUTHORIZED,
+        "Missing or invalid credentials",
+        "Unauthorized",
+    )
+    .await;
+    info(&ctx, Some(2)).await;
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::POST,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: false,
+        },
+    )
+    .await;
+
+    // JSON body checks
+    json_body_checks(&ctx, api_info.clone()).await;
+
+    // invalid token
+    let other_root_keypair = KeyPair::new();
+    let invalid_tokens = [
+        s!(""),
+        s!("not a token"),
+        create_token(
+            &other_root_keypair,
+            Role::Cosigner(ctx.cosigners[0].0.clone()),
+            None,
+        ),
+    ];
+    for token in invalid_tokens {
+        let req = RevokeTokenRequest { token };
+        let res = reqwest::Client::new()
+            .post(format!("http://{}/{}", ctx.node_address, PATH))
+            .bearer_auth(ctx.get_cosigner_token(0))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_nok(
+            res,
+            reqwest::StatusCode::BAD_REQUEST,
+            "Invalid token: not a valid token issued with the root key",
+            "InvalidToken",
+        )
+        .await;
+    }
+
+    // tokens of other cosigners and watch-only tokens cannot be revoked
+    for token in [ctx.get_cosigner_token(1), ctx.watch_only_token.clone()] {
+        let req = RevokeTokenRequest { token };
+        let res = reqwest::Client::new()
+            .post(format!("http://{}/{}", ctx.node_address, PATH))
+            .bearer_auth(ctx.get_cosigner_token(0))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_nok(
+            res,
+            reqwest::StatusCode::FORBIDDEN,
+            "Cannot revoke token: token doesn't belong to the requesting cosigner",
+            "CannotRevokeToken",
+        )
+        .await;
+    }
+    info(&ctx, Some(1)).await;
+    info(&ctx, None).await;
+
+    // already revoked
+    let xpub = ctx.cosigners[0].0.clone();
+    let other_token = create_token(&ctx.root_keypair, Role::Cosigner(xpub), None);
+    revoke_token(&ctx, other_token.clone(), 0).await;
+    let req = RevokeTokenRequest { token: other_token };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot revoke token: token already revoked",
+        "CannotRevokeToken",
+    )
+    .await;
+}
@@ -0,0 +1,161 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/spending_limits/";
+
+// amount leaving the multisig with the test PSBT (sent amount + fee)
+const PSBT_OUTFLOW: u64 = 1500;
+
+async fn approve_operation(ctx: &TestContext, operation_idx: i32, ack_count: i32) {
+    for cosigner_idx in 1..=ack_count {
+        let form =
+            respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(cosigner_idx)));
+        respond_to_operation(ctx, form, cosigner_idx).await;
+    }
+    let res = get_operation_by_idx(ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.status, OperationStatus::Approved);
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(ctx, operation_idx, cosigner_idx).await;
+    }
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.spending_limits = vec![
+            SpendingLimitConfig {
+                window: SpendingLimitWindow::Daily,
+                amount_sat: 2000,
+                threshold: Some(4),
+                ..Default::default()
+            },
+            SpendingLimitConfig {
+                window: SpendingLimitWindow::Weekly,
+                amount_sat: 3000,
+                reject: true,
+                ..Default::default()
+            },
+        ];
+    })
+    .await;
+
+    // nothing spent yet
+    let res = info(&ctx, None).await;
+    assert_eq!(res.spending_limits.len(), 2);
+    assert_eq!(res.spending_limits[0].window, SpendingLimitWindow::Daily);
+    assert_eq!(res.spending_limits[0].limit_sat, 2000);
+    assert_eq!(res.spending_limits[0].used_sat, 0);
+    assert_eq!(res.spending_limits[0].remaining_sat, 2000);
+    assert_eq!(res.spending_limits[1].window, SpendingLimitWindow::Weekly);
+    assert_eq!(res.spending_limits[1].remaining_sat, 3000);
+
+    // operation within the allowances keeps the threshold of its type
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.threshold, Some(3));
+
+    // outflows are only tracked once approved
+    assert_eq!(info(&ctx, None).await.spending_limits[0].used_sat, 0);
+    approve_operation(&ctx, operation_idx, 2).await;
+    let res = info(&ctx, None).await;
+    assert_eq!(res.spending_limits[0].used_sat, PSBT_OUTFLOW);
+    assert_eq!(res.spending_limits[0].remaining_sat, 2000 - PSBT_OUTFLOW);
+    assert_eq!(res.spending_limits[1].used_sat, PSBT_OUTFLOW);
+    assert_eq!(res.spending_limits[1].remaining_sat, 3000 - PSBT_OUTFLOW);
+
+    // exceeding the daily allowance raises the threshold
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.threshold, Some(4));
+    approve_operation(&ctx, operation_idx, 3).await;
+    let res = info(&ctx, None).await;
+    assert_eq!(res.spending_limits[0].used_sat, 2 * PSBT_OUTFLOW);
+    assert_eq!(res.spending_limits[0].remaining_sat, 0);
+    assert_eq!(res.spending_limits[1].remaining_sat, 0);
+
+    // operations without outflows are not affected
+    let address = Address::p2wsh(&ScriptBuf::new(), Network::Regtest).to_string();
+    let form = update_allowlist_form(vec![address], vec![]);
+    let operation_idx = post_operation_with_multipart_form(&ctx, form, 0)
+        .await
+        .operation_idx;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.threshold, Some(3));
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.spending_limits = vec![SpendingLimitConfig {
+            window: SpendingLimitWindow::Weekly,
+            amount_sat: PSBT_OUTFLOW - 1,
+            reject: true,
+            ..Default::default()
+        }];
+    })
+    .await;
+
+    // operation exceeding the allowance is rejected
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendBtc as u8).to_le_bytes().to_vec());
+    let psbt_part = multipart::Part::bytes(signed_psbt_bytes(0));
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_psbt", psbt_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/postoperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Spending limit exceeded: outflow of 1500 sats exceeds the remaining allowance of 1499 sats of spending limit #1",
+        "SpendingLimitExceeded",
+    )
+    .await;
+
+    // operation whose outflow is unknown is rejected too
+    let operation_type_part =
+        multipart::Part::bytes((OperationType::SendRgb as u8).to_le_bytes().to_vec());
+    let consignment_part = multipart::Part::bytes(vec![1, 2, 3]);
+    let form = multipart::Form::new()
+        .part("operation_type", operation_type_part)
+        .part("file_consignment", consignment_part);
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/postoperation", ctx.node_address))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Spending limit exceeded: unknown outflow exceeds the remaining allowance of 1499 sats of spending limit #1",
+        "SpendingLimitExceeded",
+    )
+    .await;
+    assert!(info(&ctx, None).await.last_operation_idx.is_none());
+}
@@ -0,0 +1,179 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/sync_operations/";
+
+const PATH: &str = "syncoperations";
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let (ctx, approved_op_idx) = setup_with_approved_operation(&app_dir).await;
+    mark_operation_processed(&ctx, approved_op_idx, 0).await;
+    let issuance_op_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+
+    // all operations, with the requesting cosigner's response and files
+    let res = sync_operations(&ctx, 0, Some(1)).await;
+    let operation_idxs: Vec<i32> = res.operations.iter().map(|o| o.operation_idx).collect();
+    assert_eq!(operation_idxs, vec![approved_op_idx, issuance_op_idx]);
+    assert!(!res.has_more);
+    let approved_op = get_operation_by_idx(&ctx, approved_op_idx, Some(1))
+        .await
+        .unwrap();
+    assert_eq!(res.operations[0].my_response, Some(true));
+    assert!(res.operations[0].processed_at.is_none());
+    assert_eq!(res.operations[0].files.len(), approved_op.files.len());
+    let res = sync_operations(&ctx, 0, Some(0)).await;
+    assert!(res.operations[0].processed_at.is_some());
+
+    // only the operations after the given index
+    let res = sync_operations(&ctx, approved_op_idx, None).await;
+    assert_eq!(res.operations.len(), 1);
+    assert_eq!(res.operations[0].operation_idx, issuance_op_idx);
+    let res = sync_operations(&ctx, issuance_op_idx, None).await;
+    assert!(res.operations.is_empty());
+
+    // as a tar archive, including the files
+    let req = SyncOperationsRequest {
+        after_idx: 0,
+        archive: true,
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    let res = check_response_is_ok(res).await;
+    assert_eq!(
+        res.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/x-tar"
+    );
+    let content_length = res.content_length().unwrap();
+    let bytes = res.bytes().await.unwrap();
+    assert_eq!(bytes.len() as u64, content_length);
+    let mut archive = tar::Archive::new(&bytes[..]);
+    let mut entries = Vec::new();
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let path = entry.path().unwrap().to_string_lossy().to_string();
+        let mut content = Vec::new();
+        std::io::Read::read_to_end(&mut entry, &mut content).unwrap();
+        entries.push((path, content));
+    }
+    assert_eq!(entries[0].0, SYNC_ARCHIVE_OPERATIONS_PATH);
+    let archived: SyncOperationsResponse = serde_json::from_slice(&entries[0].1).unwrap();
+    let operation_idxs: Vec<i32> = archived
+        .operations
+        .iter()
+        .map(|o| o.operation_idx)
+        .collect();
+    assert_eq!(operation_idxs, vec![approved_op_idx, issuance_op_idx]);
+    assert_eq!(archived.operations[0].my_response, Some(true));
+    let file_ids: HashSet<String> = archived
+        .operations
+        .iter()
+        .flat_map(|o| o.files.iter().map(|f| f.file_id.clone()))
+        .collect();
+    assert_eq!(entries.len(), file_ids.len() + 1);
+    for (path, content) in &entries[1..] {
+        let file_id = path
+            .strip_prefix(&format!("{SYNC_ARCHIVE_FILES_DIR}/"))
+            .unwrap();
+        assert!(file_ids.contains(file_id));
+        let res = get_file(&ctx, file_id.to_string(), Some(1)).await;
+        assert_eq!(res.bytes().await.unwrap(), content.as_slice());
+    }
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn has_more() {
+    let app_dir = format!("{TEST_DIR_BASE}has_more");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    // a single client is reused, as building one for each of the many requests is slow
+    let client = reqwest::Client::new();
+    let num_operations = MAX_SYNC_OPERATIONS as i32 + 1;
+    for operation_idx in 1..=num_operations {
+        let operation_type_part =
+            multipart::Part::bytes((OperationType::Issuance as u8).to_le_bytes().to_vec());
+        let form = multipart::Form::new()
+            .part("operation_type", operation_type_part)
+            .part("file_consignment", multipart::Part::bytes(vec![1, 2, 3]));
+        let res = client
+            .post(format!("http://{}/postoperation", ctx.node_address))
+            .bearer_auth(ctx.get_cosigner_token(0))
+            .multipart(form)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_ok(res).await;
+        let req = MarkOperationProcessedRequest {
+            operation_idx,
+            result: None,
+        };
+        let res = client
+            .post(format!(
+                "http://{}/markoperationprocessed",
+                ctx.node_address
+            ))
+            .bearer_auth(ctx.get_cosigner_token(0))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        check_response_is_ok(res).await;
+    }
+
+    // at most the maximum number of operations is returned
+    let res = sync_operations(&ctx, 0, None).await;
+    assert_eq!(res.operations.len(), MAX_SYNC_OPERATIONS as usize);
+    assert!(res.has_more);
+    let operation_idxs: Vec<i32> = res.operations.iter().map(|o| o.operation_idx).collect();
+    assert_eq!(
+        operation_idxs,
+        (1..=MAX_SYNC_OPERATIONS as i32).collect::<Vec<_>>()
+    );
+
+    // the sync resumes from the last returned operation
+    let last_idx = res.operations.last().unwrap().operation_idx;
+    let res = sync_operations(&ctx, last_idx, None).await;
+    assert_eq!(res.operations.len(), 1);
+    assert_eq!(res.operations[0].operation_idx, num_operations);
+    assert!(!res.has_more);
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::POST,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: true,
+        },
+    )
+    .await;
+
+    // JSON body checks
+    json_body_checks(&ctx, api_info.clone()).await;
+}
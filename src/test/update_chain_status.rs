@@ -0,0 +1,322 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/update_chain_status/";
+
+const PATH: &str = "updatechainstatus";
+
+fn test_txid(byte: u8) -> String {
+    Txid::from_byte_array([byte; 32]).to_string()
+}
+
+async fn check_update_chain_status_is_nok(
+    ctx: &TestContext,
+    req: &UpdateChainStatusRequest,
+    expected_status: reqwest::StatusCode,
+    expected_message: &str,
+    expected_name: &str,
+) {
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(res, expected_status, expected_message, expected_name).await;
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let (ctx, operation_idx) = setup_with_approved_operation(&app_dir).await;
+    let txid = test_txid(1);
+
+    // no chain status after approval
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert!(res.chain_status.is_none());
+    assert!(res.txid.is_none());
+    assert!(res.block_height.is_none());
+
+    // broadcast
+    let req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Broadcast,
+        txid: Some(txid.clone()),
+        block_height: None,
+    };
+    let res = update_chain_status(&ctx, &req, 0).await;
+    assert_eq!(res.chain_status, Some(ChainStatus::Broadcast));
+    assert_eq!(res.txid, Some(txid.clone()));
+    assert!(res.block_height.is_none());
+
+    // confirmed, reported by another cosigner without repeating the txid
+    let req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Confirmed,
+        txid: None,
+        block_height: Some(100),
+    };
+    let res = update_chain_status(&ctx, &req, 1).await;
+    assert_eq!(res.chain_status, Some(ChainStatus::Confirmed));
+    assert_eq!(res.txid, Some(txid));
+    assert_eq!(res.block_height, Some(100));
+
+    // events (posted, 2 responses and approved before)
+    let mut events_res = subscribe_events(&ctx, Some(4), None).await;
+    let events = read_events(&mut events_res, 2).await;
+    assert_eq!(events[0].event_type, EventType::OperationBroadcast);
+    assert_eq!(events[0].cosigner_xpub, Some(ctx.cosigners[0].0.clone()));
+    assert_eq!(events[1].event_type, EventType::OperationConfirmed);
+    assert_eq!(events[1].cosigner_xpub, Some(ctx.cosigners[1].0.clone()));
+
+    // failed, without a previous broadcast
+    mark_operation_processed(&ctx, operation_idx, 0).await;
+    let operation_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+    let req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Failed,
+        txid: None,
+        block_height: None,
+    };
+    let res = update_chain_status(&ctx, &req, 0).await;
+    assert_eq!(res.chain_status, Some(ChainStatus::Failed));
+    assert!(res.txid.is_none());
+    assert!(res.block_height.is_none());
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn chain_source() {
+    let app_dir = format!("{TEST_DIR_BASE}chain_source");
+
+    let esplora = start_mock_esplora().await;
+    let ctx = setup_daemon_with_params(&app_dir, |params| {
+        params.chain_source = Some(ChainSourceConfig {
+            esplora_url: esplora.url.clone(),
+            poll_interval: 1,
+        });
+    })
+    .await;
+    let txid = test_txid(1);
+
+    // broadcast an approved operation (posted and approved events)
+    let operation_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+    let req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Broadcast,
+        txid: Some(txid.clone()),
+        block_height: None,
+    };
+    update_chain_status(&ctx, &req, 0).await;
+
+    // unknown and unconfirmed transactions are left as broadcast
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    esplora.set_tx(&txid, None);
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.chain_status, Some(ChainStatus::Broadcast));
+
+    // the confirmation is picked up automatically
+    let mut events_res = subscribe_events(&ctx, Some(3), None).await;
+    esplora.set_tx(&txid, Some(150));
+    let events = read_events(&mut events_res, 1).await;
+    assert_eq!(events[0].event_type, EventType::OperationConfirmed);
+    assert_eq!(events[0].operation_idx, operation_idx);
+    assert!(events[0].cosigner_xpub.is_none());
+    let res = get_operation_by_idx(&ctx, operation_idx, None)
+        .await
+        .unwrap();
+    assert_eq!(res.chain_status, Some(ChainStatus::Confirmed));
+    assert_eq!(res.txid, Some(txid));
+    assert_eq!(res.block_height, Some(150));
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let ctx = setup_daemon(&app_dir).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::POST,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: false,
+        },
+    )
+    .await;
+
+    // JSON body checks
+    json_body_checks(&ctx, api_info.clone()).await;
+
+    let operation_idx = post_operation(&ctx, OperationType::Issuance)
+        .await
+        .operation_idx;
+
+    // invalid requests
+    let invalid_requests = [
+        (
+            ChainStatus::Broadcast,
+            Some(s!("invalid")),
+            None,
+            "invalid txid 'invalid'",
+        ),
+        (
+            ChainStatus::Broadcast,
+            None,
+            None,
+            "a txid is required to report a broadcast",
+        ),
+        (
+            ChainStatus::Confirmed,
+            Some(test_txid(1)),
+            None,
+            "a block height is required to report a confirmation",
+        ),
+        (
+            ChainStatus::Broadcast,
+            Some(test_txid(1)),
+            Some(100),
+            "a block height can only be reported with a confirmation",
+        ),
+        (
+            ChainStatus::Failed,
+            None,
+            Some(100),
+            "a block height can only be reported with a confirmation",
+        ),
+        (
+            ChainStatus::Confirmed,
+            None,
+            Some(100),
+            "a txid is required to report a confirmation",
+        ),
+    ];
+    for (chain_status, txid, block_height, expected) in invalid_requests {
+        let req = UpdateChainStatusRequest {
+            operation_idx,
+            chain_status,
+            txid,
+            block_height,
+        };
+        check_update_chain_status_is_nok(
+            &ctx,
+            &req,
+            reqwest::StatusCode::BAD_REQUEST,
+            &format!("Invalid request: {expected}"),
+            "InvalidRequest",
+        )
+        .await;
+    }
+
+    // non-existent operation
+    let req = UpdateChainStatusRequest {
+        operation_idx: 9999,
+        chain_status: ChainStatus::Failed,
+        txid: None,
+        block_height: None,
+    };
+    check_update_chain_status_is_nok(
+        &ctx,
+        &req,
+        reqwest::StatusCode::BAD_REQUEST,
+        "Operation not found",
+        "OperationNotFound",
+    )
+    .await;
+
+    // transitions that don't move forward
+    let broadcast_req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Broadcast,
+        txid: Some(test_txid(1)),
+        block_height: None,
+    };
+    update_chain_status(&ctx, &broadcast_req, 0).await;
+    check_update_chain_status_is_nok(
+        &ctx,
+        &broadcast_req,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot update chain status: operation is already Broadcast",
+        "CannotUpdateChainStatus",
+    )
+    .await;
+    let mut confirmed_req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Confirmed,
+        txid: Some(test_txid(2)),
+        block_height: Some(100),
+    };
+    check_update_chain_status_is_nok(
+        &ctx,
+        &confirmed_req,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot update chain status: txid differs from the one already reported",
+        "CannotUpdateChainStatus",
+    )
+    .await;
+    confirmed_req.txid = Some(test_txid(1));
+    update_chain_status(&ctx, &confirmed_req, 0).await;
+    check_update_chain_status_is_nok(
+        &ctx,
+        &confirmed_req,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot update chain status: operation is already Confirmed",
+        "CannotUpdateChainStatus",
+    )
+    .await;
+    let failed_req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Failed,
+        txid: None,
+        block_height: None,
+    };
+    check_update_chain_status_is_nok(
+        &ctx,
+        &failed_req,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot update chain status: operation is already Confirmed",
+        "CannotUpdateChainStatus",
+    )
+    .await;
+
+    // operation that is not approved
+    mark_operation_processed(&ctx, operation_idx, 0).await;
+    let operation_idx = post_operation(&ctx, OperationType::SendRgb)
+        .await
+        .operation_idx;
+    let req = UpdateChainStatusRequest {
+        operation_idx,
+        chain_status: ChainStatus::Failed,
+        txid: None,
+        block_height: None,
+    };
+    check_update_chain_status_is_nok(
+        &ctx,
+        &req,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot update chain status: only approved operations can be tracked on chain",
+        "CannotUpdateChainStatus",
+    )
+    .await;
+}
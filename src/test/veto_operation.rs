@@ -0,0 +1,129 @@
+use super::*;
+
+const TEST_DIR_BASE: &str = "tmp/veto_operation/";
+
+const PATH: &str = "vetooperation";
+
+async fn setup_with_delayed_operation(app_dir: &str, approval_delay: u64) -> (TestContext, i32) {
+    let ctx = setup_daemon_with_params(app_dir, |params| {
+        params.approval_delays = HashMap::from([(OperationType::SendBtc, approval_delay)]);
+    })
+    .await;
+    let operation_idx = post_operation(&ctx, OperationType::SendBtc)
+        .await
+        .operation_idx;
+    for cosigner_idx in 1..3 {
+        let form =
+            respond_to_operation_form(operation_idx, true, Some(signed_psbt_bytes(cosigner_idx)));
+        respond_to_operation(&ctx, form, cosigner_idx).await;
+    }
+    (ctx, operation_idx)
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn success() {
+    let app_dir = format!("{TEST_DIR_BASE}success");
+
+    let (ctx, operation_idx) = setup_with_delayed_operation(&app_dir, 60).await;
+
+    // any cosigner can veto, including one that ACKed the operation
+    let res = veto_operation(&ctx, operation_idx, 1).await;
+    assert_eq!(res.operation_idx, operation_idx);
+    assert_eq!(res.status, OperationStatus::Vetoed);
+    assert_eq!(res.my_response, Some(true));
+    assert!(res.files.iter().all(|f| f.r#type != FileType::CombinedPsbt));
+
+    // all cosigners can mark the vetoed operation as processed
+    for cosigner_idx in 0..ctx.num_cosigners() {
+        mark_operation_processed(&ctx, operation_idx, cosigner_idx).await;
+    }
+
+    // a new operation can be posted
+    let res = post_operation(&ctx, OperationType::SendRgb).await;
+    assert_eq!(res.operation_idx, operation_idx + 1);
+}
+
+#[serial_test::serial]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[traced_test]
+async fn fail() {
+    let app_dir = format!("{TEST_DIR_BASE}fail");
+
+    let (ctx, operation_idx) = setup_with_pending_operation(&app_dir).await;
+
+    let api_info = APIInfo {
+        method: reqwest::Method::POST,
+        path: PATH.to_string(),
+    };
+
+    // token checks
+    token_checks(
+        &ctx,
+        TokenChecks {
+            api_info: api_info.clone(),
+            allows_watch_only: false,
+        },
+    )
+    .await;
+
+    // JSON body checks
+    json_body_checks(&ctx, api_info.clone()).await;
+
+    // non-existent operation
+    let req = VetoOperationRequest {
+        operation_idx: 9999,
+    };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(0))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::BAD_REQUEST,
+        "Operation not found",
+        "OperationNotFound",
+    )
+    .await;
+
+    // operation not in its approval delay
+    let req = VetoOperationRequest { operation_idx };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(1))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot veto operation: operation is not in its approval delay",
+        "CannotVetoOperation",
+    )
+    .await;
+
+    // operation already vetoed
+    let app_dir = format!("{TEST_DIR_BASE}fail_vetoed");
+    let (ctx, operation_idx) = setup_with_delayed_operation(&app_dir, 60).await;
+    veto_operation(&ctx, operation_idx, 0).await;
+    let req = VetoOperationRequest { operation_idx };
+    let res = reqwest::Client::new()
+        .post(format!("http://{}/{}", ctx.node_address, PATH))
+        .bearer_auth(ctx.get_cosigner_token(2))
+        .json(&req)
+        .send()
+        .await
+        .unwrap();
+    check_response_is_nok(
+        res,
+        reqwest::StatusCode::FORBIDDEN,
+        "Cannot veto operation: operation is not in its approval delay",
+        "CannotVetoOperation",
+    )
+    .await;
+}
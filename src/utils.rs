@@ -3,6 +3,8 @@ use std::{
     path::Path,
 };
 
+use bitcoin::{Address, address::NetworkUnchecked, base58, bip32::Xpub};
+use hmac::{Hmac, Mac};
 use sha2::{Digest, Sha256};
 use tempfile::NamedTempFile;
 use time::OffsetDateTime;
@@ -10,7 +12,7 @@ use tokio::{fs::File, io::AsyncReadExt};
 
 use crate::{
     error::{APIError, AppError},
-    routes::OperationType,
+    routes::{BitcoinNetwork, OperationType},
 };
 
 pub(crate) fn check_port_is_available(port: u16) -> Result<(), AppError> {
@@ -52,6 +54,45 @@ pub(crate) fn hex_str_to_vec(hex: &str) -> Option<Vec<u8>> {
     Some(out)
 }
 
+// SLIP-132 version bytes, mapped to the corresponding standard xpub/tpub ones
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+const TPUB_VERSION: [u8; 4] = [0x04, 0x35, 0x87, 0xCF];
+const SLIP132_VERSIONS: [([u8; 4], [u8; 4]); 8] = [
+    ([0x04, 0x9D, 0x7C, 0xB2], XPUB_VERSION), // ypub
+    ([0x04, 0xB2, 0x47, 0x46], XPUB_VERSION), // zpub
+    ([0x02, 0x95, 0xB4, 0x3F], XPUB_VERSION), // Ypub
+    ([0x02, 0xAA, 0x7E, 0xD3], XPUB_VERSION), // Zpub
+    ([0x04, 0x4A, 0x52, 0x62], TPUB_VERSION), // upub
+    ([0x04, 0x5F, 0x1C, 0xF6], TPUB_VERSION), // vpub
+    ([0x02, 0x42, 0x89, 0xEF], TPUB_VERSION), // Upub
+    ([0x02, 0x57, 0x54, 0x83], TPUB_VERSION), // Vpub
+];
+
+pub(crate) fn parse_xpub(xpub: &str) -> Option<Xpub> {
+    let mut data = base58::decode_check(xpub).ok()?;
+    if data.len() < 4 {
+        return None;
+    }
+    if let Some((_, version)) = SLIP132_VERSIONS.iter().find(|(v, _)| *v == data[..4]) {
+        data[..4].copy_from_slice(version);
+    }
+    Xpub::decode(&data).ok()
+}
+
+// returns the address in its canonical form
+pub(crate) fn parse_address(address: &str, network: BitcoinNetwork) -> Result<String, APIError> {
+    Ok(address
+        .parse::<Address<NetworkUnchecked>>()
+        .map_err(|_| APIError::InvalidRequest(format!("'{address}' is not a valid address")))?
+        .require_network(network.network())
+        .map_err(|_| {
+            APIError::NetworkMismatch(format!(
+                "'{address}' does not belong to the {network:?} network"
+            ))
+        })?
+        .to_string())
+}
+
 pub(crate) fn now() -> OffsetDateTime {
     OffsetDateTime::now_utc()
 }
@@ -70,20 +111,62 @@ pub(crate) async fn compute_file_id(path: &Path) -> Result<String, APIError> {
     Ok(hex::encode(hasher.finalize()))
 }
 
+const TAR_BLOCK_SIZE: u64 = 512;
+
+// header of a tar archive entry for a regular file
+pub(crate) fn tar_entry_header(path: &str, size: u64, mtime: u64) -> Result<Vec<u8>, APIError> {
+    let mut header = tar::Header::new_ustar();
+    header.set_path(path)?;
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(size);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    Ok(header.as_bytes().to_vec())
+}
+
+// zeros filling the last block of a tar archive entry
+pub(crate) fn tar_entry_padding(size: u64) -> Vec<u8> {
+    vec![0; ((TAR_BLOCK_SIZE - size % TAR_BLOCK_SIZE) % TAR_BLOCK_SIZE) as usize]
+}
+
+// total size of a tar archive entry, header and padding included
+pub(crate) fn tar_entry_len(size: u64) -> u64 {
+    TAR_BLOCK_SIZE + size.div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE
+}
+
+// zeros marking the end of a tar archive
+pub(crate) fn tar_end() -> Vec<u8> {
+    vec![0; 2 * TAR_BLOCK_SIZE as usize]
+}
+
 pub(crate) fn get_threshold_for_operation(
     op_type: &OperationType,
     threshold_vanilla: u8,
     threshold_colored: u8,
+    auto_approved_ops: &[OperationType],
 ) -> Option<u8> {
+    if auto_approved_ops.contains(op_type) {
+        return None;
+    }
     match op_type {
         OperationType::CreateUtxos | OperationType::SendBtc => Some(threshold_vanilla),
-        OperationType::SendRgb | OperationType::Inflation => Some(threshold_colored),
-        OperationType::Issuance | OperationType::BlindReceive | OperationType::WitnessReceive => {
-            None
-        }
+        OperationType::SendRgb
+        | OperationType::Inflation
+        | OperationType::Issuance
+        | OperationType::BlindReceive
+        | OperationType::WitnessReceive => Some(threshold_colored),
+        OperationType::UpdateAllowlist => Some(threshold_vanilla.max(threshold_colored)),
     }
 }
 
+pub(crate) fn sign_webhook_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
 pub(crate) async fn persist_temp_file(
     temp_file: NamedTempFile,
     file_path: &Path,
@@ -105,7 +188,7 @@ pub(crate) async fn persist_temp_file(
 
 #[cfg(test)]
 mod tests {
-    use crate::routes::AUTO_APPROVED_OPS;
+    use crate::routes::DEFAULT_AUTO_APPROVED_OPS;
 
     use super::*;
 
@@ -113,43 +196,114 @@ mod tests {
     fn test_get_threshold_for_operation() {
         let threshold_vanilla = 1;
         let threshold_colored = 2;
-        for op_type in AUTO_APPROVED_OPS {
-            assert_eq!(
-                get_threshold_for_operation(&op_type, threshold_vanilla, threshold_colored),
-                None
-            );
-        }
-        assert_eq!(
+        let get_threshold = |op_type| {
             get_threshold_for_operation(
-                &OperationType::CreateUtxos,
+                &op_type,
                 threshold_vanilla,
-                threshold_colored
-            ),
+                threshold_colored,
+                &DEFAULT_AUTO_APPROVED_OPS,
+            )
+        };
+        for op_type in DEFAULT_AUTO_APPROVED_OPS {
+            assert_eq!(get_threshold(op_type), None);
+        }
+        assert_eq!(
+            get_threshold(OperationType::CreateUtxos),
             Some(threshold_vanilla)
         );
         assert_eq!(
-            get_threshold_for_operation(
-                &OperationType::SendBtc,
-                threshold_vanilla,
-                threshold_colored
-            ),
+            get_threshold(OperationType::SendBtc),
             Some(threshold_vanilla)
         );
+        assert_eq!(
+            get_threshold(OperationType::SendRgb),
+            Some(threshold_colored)
+        );
+        assert_eq!(
+            get_threshold(OperationType::Inflation),
+            Some(threshold_colored)
+        );
+        assert_eq!(
+            get_threshold(OperationType::UpdateAllowlist),
+            Some(threshold_colored)
+        );
+
+        // types that are not auto-approved require approval
         assert_eq!(
             get_threshold_for_operation(
-                &OperationType::SendRgb,
+                &OperationType::Issuance,
                 threshold_vanilla,
-                threshold_colored
+                threshold_colored,
+                &[OperationType::SendBtc],
             ),
             Some(threshold_colored)
         );
         assert_eq!(
             get_threshold_for_operation(
-                &OperationType::Inflation,
+                &OperationType::SendBtc,
                 threshold_vanilla,
-                threshold_colored
+                threshold_colored,
+                &[OperationType::SendBtc],
             ),
-            Some(threshold_colored)
+            None
         );
     }
+
+    #[test]
+    fn test_parse_address() {
+        let script = bitcoin::ScriptBuf::new();
+        let regtest_address = Address::p2wsh(&script, bitcoin::Network::Regtest).to_string();
+        assert_eq!(
+            parse_address(&regtest_address.to_uppercase(), BitcoinNetwork::Regtest).unwrap(),
+            regtest_address
+        );
+        let mainnet_address = Address::p2wsh(&script, bitcoin::Network::Bitcoin).to_string();
+        assert!(matches!(
+            parse_address(&mainnet_address, BitcoinNetwork::Regtest),
+            Err(APIError::NetworkMismatch(_))
+        ));
+        assert!(matches!(
+            parse_address("address", BitcoinNetwork::Regtest),
+            Err(APIError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_webhook_payload() {
+        assert_eq!(
+            sign_webhook_payload("key", b"The quick brown fox jumps over the lazy dog"),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_parse_xpub() {
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        let tpub = "tpubD6NzVbkrYhZ4XJ6aDsDYTCUkn1QqC6ie7eappEWB823FLSsRo1VBoEmtQVPJEJYdBt1UArW74BJg54FbW217Xoae6SDgj71JQZTfYCSJUyy";
+        let parsed_xpub = parse_xpub(xpub).unwrap();
+        assert_eq!(parsed_xpub.network, bitcoin::NetworkKind::Main);
+        let parsed_tpub = parse_xpub(tpub).unwrap();
+        assert_eq!(parsed_tpub.network, bitcoin::NetworkKind::Test);
+
+        // SLIP-132 variants encode the same key as the standard version
+        for (slip132_version, standard_version) in SLIP132_VERSIONS {
+            let (standard, parsed) = if standard_version == XPUB_VERSION {
+                (xpub, parsed_xpub)
+            } else {
+                (tpub, parsed_tpub)
+            };
+            let mut data = base58::decode_check(standard).unwrap();
+            data[..4].copy_from_slice(&slip132_version);
+            let variant = base58::encode_check(&data);
+            assert_eq!(parse_xpub(&variant).unwrap(), parsed);
+        }
+
+        // invalid
+        assert!(parse_xpub("xpub1").is_none());
+        assert!(parse_xpub("").is_none());
+        assert!(parse_xpub(&xpub[..xpub.len() - 1]).is_none());
+        let mut data = base58::decode_check(xpub).unwrap();
+        data[..4].copy_from_slice(&[0, 0, 0, 0]);
+        assert!(parse_xpub(&base58::encode_check(&data)).is_none());
+    }
 }
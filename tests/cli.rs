@@ -44,7 +44,12 @@ fn valid_startup() {
     let temp_dir = TempDir::new().unwrap();
     let storage_path = temp_dir.path();
     let config_content = r#"
-cosigner_xpubs = ["xpub1", "xpub2", "xpub3"]
+network = "Regtest"
+cosigner_xpubs = [
+    "tpubD6NzVbkrYhZ4XJ6aDsDYTCUkn1QqC6ie7eappEWB823FLSsRo1VBoEmtQVPJEJYdBt1UArW74BJg54FbW217Xoae6SDgj71JQZTfYCSJUyy",
+    "tpubD6NzVbkrYhZ4XoJ4SGokACCMyKUYycuuu4tNDAW9qQrksXPNU9C9jeqQJQsdd18Dgt5v2hcc1w4qjNqYQg4nJ15YQNBHsWUuv2cEmneU7Mn",
+    "tpubD6NzVbkrYhZ4WYbMkJwEwwTsQfjND3xNcXF6MoG7Ge8DbP8yWAkeg7DKPcuYfuHZYxCGWg9bFsAKLvJjb66LRM1wAkeszXKNAZdPpwnfHtd",
+]
 threshold_colored = 2
 threshold_vanilla = 2
 root_public_key = "0000000000000000000000000000000000000000000000000000000000000000"
@@ -64,13 +69,65 @@ rgb_lib_version = "0.3"
     assert!(storage_path.join("logs").exists());
 }
 
+#[test]
+fn network_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let storage_path = temp_dir.path();
+    let config_content = r#"
+cosigner_xpubs = [
+    "tpubD6NzVbkrYhZ4XJ6aDsDYTCUkn1QqC6ie7eappEWB823FLSsRo1VBoEmtQVPJEJYdBt1UArW74BJg54FbW217Xoae6SDgj71JQZTfYCSJUyy",
+    "tpubD6NzVbkrYhZ4XoJ4SGokACCMyKUYycuuu4tNDAW9qQrksXPNU9C9jeqQJQsdd18Dgt5v2hcc1w4qjNqYQg4nJ15YQNBHsWUuv2cEmneU7Mn",
+    "tpubD6NzVbkrYhZ4WYbMkJwEwwTsQfjND3xNcXF6MoG7Ge8DbP8yWAkeg7DKPcuYfuHZYxCGWg9bFsAKLvJjb66LRM1wAkeszXKNAZdPpwnfHtd",
+]
+threshold_colored = 2
+threshold_vanilla = 2
+root_public_key = "0000000000000000000000000000000000000000000000000000000000000000"
+rgb_lib_version = "0.3"
+"#;
+    fs::write(
+        storage_path.join("config.toml"),
+        format!("network = \"Testnet\"{config_content}"),
+    )
+    .unwrap();
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_rgb-multisig-bridge"));
+    let output = cmd
+        .arg(storage_path.to_str().unwrap())
+        .arg("--daemon-listening-port")
+        .arg("0")
+        .timeout(std::time::Duration::from_secs(2))
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Listening on"));
+
+    // restart on a different network
+    fs::write(
+        storage_path.join("config.toml"),
+        format!("network = \"Regtest\"{config_content}"),
+    )
+    .unwrap();
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_rgb-multisig-bridge"));
+    cmd.arg(storage_path.to_str().unwrap())
+        .arg("--daemon-listening-port")
+        .arg("0")
+        .timeout(std::time::Duration::from_secs(2))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot change network"));
+}
+
 #[test]
 #[cfg(unix)]
 fn sigterm_shutdown() {
     let temp_dir = TempDir::new().unwrap();
     let storage_path = temp_dir.path();
     let config_content = r#"
-cosigner_xpubs = ["xpub1", "xpub2", "xpub3"]
+network = "Regtest"
+cosigner_xpubs = [
+    "tpubD6NzVbkrYhZ4XJ6aDsDYTCUkn1QqC6ie7eappEWB823FLSsRo1VBoEmtQVPJEJYdBt1UArW74BJg54FbW217Xoae6SDgj71JQZTfYCSJUyy",
+    "tpubD6NzVbkrYhZ4XoJ4SGokACCMyKUYycuuu4tNDAW9qQrksXPNU9C9jeqQJQsdd18Dgt5v2hcc1w4qjNqYQg4nJ15YQNBHsWUuv2cEmneU7Mn",
+    "tpubD6NzVbkrYhZ4WYbMkJwEwwTsQfjND3xNcXF6MoG7Ge8DbP8yWAkeg7DKPcuYfuHZYxCGWg9bFsAKLvJjb66LRM1wAkeszXKNAZdPpwnfHtd",
+]
 threshold_colored = 2
 threshold_vanilla = 2
 root_public_key = "0000000000000000000000000000000000000000000000000000000000000000"
@@ -88,7 +145,7 @@ rgb_lib_version = "0.3"
     let stdout = child.stdout.take().unwrap();
     let reader = BufReader::new(stdout);
     let mut started = false;
-    for line in reader.lines().take(100) {
+    for line in reader.lines().take(1000) {
         if let Ok(line) = line
             && line.contains("Listening on")
         {
@@ -112,7 +169,12 @@ fn sigint_shutdown() {
     let temp_dir = TempDir::new().unwrap();
     let storage_path = temp_dir.path();
     let config_content = r#"
-cosigner_xpubs = ["xpub1", "xpub2", "xpub3"]
+network = "Regtest"
+cosigner_xpubs = [
+    "tpubD6NzVbkrYhZ4XJ6aDsDYTCUkn1QqC6ie7eappEWB823FLSsRo1VBoEmtQVPJEJYdBt1UArW74BJg54FbW217Xoae6SDgj71JQZTfYCSJUyy",
+    "tpubD6NzVbkrYhZ4XoJ4SGokACCMyKUYycuuu4tNDAW9qQrksXPNU9C9jeqQJQsdd18Dgt5v2hcc1w4qjNqYQg4nJ15YQNBHsWUuv2cEmneU7Mn",
+    "tpubD6NzVbkrYhZ4WYbMkJwEwwTsQfjND3xNcXF6MoG7Ge8DbP8yWAkeg7DKPcuYfuHZYxCGWg9bFsAKLvJjb66LRM1wAkeszXKNAZdPpwnfHtd",
+]
 threshold_colored = 2
 threshold_vanilla = 2
 root_public_key = "0000000000000000000000000000000000000000000000000000000000000000"
@@ -130,7 +192,7 @@ rgb_lib_version = "0.3"
     let stdout = child.stdout.take().unwrap();
     let reader = BufReader::new(stdout);
     let mut started = false;
-    for line in reader.lines().take(100) {
+    for line in reader.lines().take(1000) {
         if let Ok(line) = line
             && line.contains("Listening on")
         {